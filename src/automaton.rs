@@ -0,0 +1,275 @@
+use crate::{Grid, Neighbour, Point, Topology};
+use rayon::prelude::*;
+
+/// A cell and its surroundings, as seen by an automaton rule.
+#[derive(Debug, Clone, Copy)]
+pub struct Neighbourhood<'a, T: Clone> {
+    grid: &'a Grid<T>,
+    point: Point,
+    topology: Topology<'a>,
+}
+
+impl<'a, T: Clone> Neighbourhood<'a, T> {
+    pub fn point(&self) -> Point {
+        self.point
+    }
+
+    /// The cell's value in the previous generation.
+    pub fn value(&self) -> &'a T {
+        &self.grid[self.point]
+    }
+
+    /// The neighbours that lie inside the grid, with their values.
+    pub fn neighbours(&self) -> impl Iterator<Item = (Neighbour, &'a T)> + 'a {
+        let grid = self.grid;
+        grid.neighbours(self.point, self.topology)
+            .map(move |neighbour| (neighbour, &grid[neighbour.point]))
+    }
+
+    /// How many neighbours match `predicate`.
+    pub fn count(&self, predicate: impl Fn(&T) -> bool) -> usize {
+        self.neighbours()
+            .filter(|(_, value)| predicate(value))
+            .count()
+    }
+
+    /// The whole previous generation, for rules that look further than the neighbours.
+    pub fn grid(&self) -> &'a Grid<T> {
+        self.grid
+    }
+}
+
+/// Runs a cellular automaton over a grid.
+///
+/// Two buffers are kept and swapped after each generation, so stepping doesn't allocate.
+#[derive(Debug, Clone)]
+pub struct Automaton<'t, T: Clone> {
+    current: Grid<T>,
+    next: Grid<T>,
+    topology: Topology<'t>,
+    generation: usize,
+}
+
+impl<'t, T: Clone + PartialEq> Automaton<'t, T> {
+    pub fn new(grid: Grid<T>, topology: Topology<'t>) -> Self {
+        Self {
+            next: grid.clone(),
+            current: grid,
+            topology,
+            generation: 0,
+        }
+    }
+
+    pub fn grid(&self) -> &Grid<T> {
+        &self.current
+    }
+
+    pub fn into_grid(self) -> Grid<T> {
+        self.current
+    }
+
+    /// The number of generations stepped so far.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Computes the next generation, returning whether any cell changed.
+    pub fn step<F>(&mut self, rule: F) -> bool
+    where
+        F: Fn(Neighbourhood<'_, T>) -> T,
+    {
+        let current = &self.current;
+        let topology = self.topology;
+        // every row has to be written, so don't stop at the first change
+        let mut changed = false;
+        for (y, row) in self.next.iter_rows_mut().enumerate() {
+            changed |= step_row(current, topology, &rule, y, row);
+        }
+        self.finish_step(changed)
+    }
+
+    /// Steps until a generation changes nothing, returning the number of generations
+    /// stepped, including the final one that changed nothing.
+    pub fn run_until_stable<F>(&mut self, rule: F) -> usize
+    where
+        F: Fn(Neighbourhood<'_, T>) -> T,
+    {
+        let start = self.generation;
+        while self.step(&rule) {}
+        self.generation - start
+    }
+
+    fn finish_step(&mut self, changed: bool) -> bool {
+        std::mem::swap(&mut self.current, &mut self.next);
+        self.generation += 1;
+        changed
+    }
+}
+
+impl<T: Clone + PartialEq + Send + Sync> Automaton<'_, T> {
+    /// Like [`Automaton::step`], but computes rows in parallel. Worth it for large grids
+    /// or expensive rules.
+    pub fn par_step<F>(&mut self, rule: F) -> bool
+    where
+        F: Fn(Neighbourhood<'_, T>) -> T + Sync,
+    {
+        let current = &self.current;
+        let topology = self.topology;
+        let changed = self
+            .next
+            .data
+            .par_chunks_mut(current.width.max(1))
+            .enumerate()
+            .map(|(y, row)| step_row(current, topology, &rule, y, row))
+            .reduce(|| false, |a, b| a || b);
+        self.finish_step(changed)
+    }
+
+    /// Like [`Automaton::run_until_stable`], but steps with [`Automaton::par_step`].
+    pub fn par_run_until_stable<F>(&mut self, rule: F) -> usize
+    where
+        F: Fn(Neighbourhood<'_, T>) -> T + Sync,
+    {
+        let start = self.generation;
+        while self.par_step(&rule) {}
+        self.generation - start
+    }
+}
+
+/// Writes row `y` of the next generation, returning whether any cell changed.
+fn step_row<T: Clone + PartialEq>(
+    current: &Grid<T>,
+    topology: Topology<'_>,
+    rule: impl Fn(Neighbourhood<'_, T>) -> T,
+    y: usize,
+    row: &mut [T],
+) -> bool {
+    let mut changed = false;
+    for (x, cell) in row.iter_mut().enumerate() {
+        let point = Point::from((x, y));
+        let value = rule(Neighbourhood {
+            grid: current,
+            point,
+            topology,
+        });
+        changed |= value != current[point];
+        *cell = value;
+    }
+    changed
+}
+
+impl<T: Clone + PartialEq> Grid<T> {
+    /// Turns this grid into an [`Automaton`] for stepping. The automaton keeps a second
+    /// buffer, so generations after the first don't allocate.
+    pub fn automaton(self, topology: Topology<'_>) -> Automaton<'_, T> {
+        Automaton::new(self, topology)
+    }
+
+    /// Replaces every cell with the result of `rule`, evaluated against the grid as it was
+    /// before the step. Returns whether any cell changed.
+    ///
+    /// The next generation is written to a new buffer that replaces this one, so the grid
+    /// is left as it was if `rule` panics. To step many times without allocating each
+    /// generation, use [`Grid::automaton`].
+    pub fn step_automaton<F>(&mut self, topology: Topology<'_>, rule: F) -> bool
+    where
+        F: Fn(Neighbourhood<'_, T>) -> T,
+    {
+        let mut next = self.clone();
+        let mut changed = false;
+        for (y, row) in next.iter_rows_mut().enumerate() {
+            changed |= step_row(self, topology, &rule, y, row);
+        }
+        *self = next;
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn life(cell: Neighbourhood<'_, bool>) -> bool {
+        let alive = cell.count(|&alive| alive);
+        alive == 3 || (alive == 2 && *cell.value())
+    }
+
+    #[test]
+    fn blinker_oscillates() {
+        let grid: Grid<char> = ".....\n..#..\n..#..\n..#..\n.....\n".parse().unwrap();
+        let grid = Grid {
+            width: 5,
+            height: 5,
+            data: grid.iter().map(|&c| c == '#').collect(),
+        };
+        let mut automaton = grid.clone().automaton(Topology::Moore);
+        assert!(automaton.step(life));
+        assert!(automaton.grid()[Point::new(1, 2)]);
+        assert!(!automaton.grid()[Point::new(2, 1)]);
+        assert!(automaton.step(life));
+        assert_eq!(automaton.grid(), &grid);
+        assert_eq!(automaton.generation(), 2);
+
+        let mut parallel = grid.clone().automaton(Topology::Moore);
+        let mut single = grid.clone().automaton(Topology::Moore);
+        assert_eq!(parallel.par_step(life), single.step(life));
+        assert_eq!(parallel.grid(), single.grid());
+        let mut stepped = grid;
+        assert!(stepped.step_automaton(Topology::Moore, life));
+        assert_eq!(&stepped, single.grid());
+    }
+
+    #[test]
+    fn runs_until_stable() {
+        // each cell becomes the largest value among itself and its neighbours
+        let grid = Grid {
+            width: 9,
+            height: 1,
+            data: vec![0, 0, 0, 0, 9, 0, 0, 0, 0],
+        };
+        let spread = |cell: Neighbourhood<'_, u32>| {
+            cell.neighbours()
+                .map(|(_, &value)| value)
+                .fold(*cell.value(), u32::max)
+        };
+        let mut automaton = grid.clone().automaton(Topology::VonNeumann);
+        assert_eq!(automaton.run_until_stable(spread), 5);
+        assert!(automaton.grid().iter().all(|&value| value == 9));
+        let mut parallel = grid.automaton(Topology::VonNeumann);
+        assert_eq!(parallel.par_run_until_stable(spread), 5);
+        assert_eq!(parallel.grid(), automaton.grid());
+    }
+
+    #[test]
+    fn sequential_steps_dont_need_send() {
+        use std::rc::Rc;
+
+        let grid = Grid {
+            width: 2,
+            height: 1,
+            data: vec![Rc::new(1), Rc::new(2)],
+        };
+        let mut automaton = grid.automaton(Topology::VonNeumann);
+        assert!(!automaton.step(|cell| cell.value().clone()));
+        assert_eq!(*automaton.grid()[Point::new(1, 0)], 2);
+    }
+
+    #[test]
+    fn step_automaton_keeps_the_grid_if_the_rule_panics() {
+        let mut grid = Grid {
+            width: 3,
+            height: 1,
+            data: vec![1, 2, 3],
+        };
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            grid.step_automaton(Topology::VonNeumann, |cell| {
+                assert!(cell.point() != Point::new(2, 0), "rule failed");
+                *cell.value() + 1
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(grid.data, [1, 2, 3]);
+        assert!(grid.step_automaton(Topology::VonNeumann, |cell| *cell.value() * 2));
+        assert_eq!(grid.data, [2, 4, 6]);
+    }
+}
@@ -1,5 +1,4 @@
-use advent_of_code::surrounding_indexes;
-use grid::Grid;
+use advent_of_code::{Grid, Point, Topology};
 use std::collections::HashMap;
 advent_of_code::solution!(3);
 
@@ -12,16 +11,21 @@ pub enum GridSpot {
     Empty,
 }
 
-impl GridSpot {
-    pub fn from_char(input: char) -> Option<Self> {
+impl TryFrom<char> for GridSpot {
+    type Error = ();
+
+    fn try_from(input: char) -> Result<Self, Self::Error> {
         match input {
-            '=' | '#' | '%' | '/' | '+' | '-' | '&' | '$' | '@' => Some(Self::Symbol),
-            '*' => Some(Self::Gear),
-            '.' => Some(Self::Empty),
-            _ => Some(Self::Num(input)),
+            '=' | '#' | '%' | '/' | '+' | '-' | '&' | '$' | '@' => Ok(Self::Symbol),
+            '*' => Ok(Self::Gear),
+            '.' => Ok(Self::Empty),
+            '0'..='9' => Ok(Self::Num(input)),
+            _ => Err(()),
         }
     }
+}
 
+impl GridSpot {
     pub fn unwrap_num(&self) -> char {
         match self {
             Self::Num(c) => *c,
@@ -35,18 +39,7 @@ impl GridSpot {
 }
 
 fn parse_to_grid(input: &str) -> Grid<GridSpot> {
-    let lines = input.lines().collect::<Vec<_>>();
-    let width = lines.first().unwrap().len();
-    let height = lines.len();
-    let mut grid = Grid::new(width, height);
-    for (y, line) in lines.iter().enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            let grid_spot = GridSpot::from_char(c).unwrap();
-            let grid_ref = grid.get_mut(y, x).unwrap();
-            *grid_ref = grid_spot;
-        }
-    }
-    grid
+    input.parse().unwrap()
 }
 
 fn resolve_number(grid: &Grid<GridSpot>, pos: (usize, usize)) -> u32 {
@@ -54,7 +47,7 @@ fn resolve_number(grid: &Grid<GridSpot>, pos: (usize, usize)) -> u32 {
 
     let mut pos = pos;
 
-    while let Some(grid_spot) = grid.get(pos.1, pos.0) {
+    while let Some(grid_spot) = grid.get(pos.0, pos.1) {
         if let GridSpot::Num(_) = grid_spot {
             vec.push(*grid_spot);
             pos.0 += 1;
@@ -72,13 +65,11 @@ fn resolve_number(grid: &Grid<GridSpot>, pos: (usize, usize)) -> u32 {
 
 pub fn valid_search_for_number(grid: &Grid<GridSpot>, pos: (usize, usize)) -> bool {
     let mut current_pos = pos;
-    while let Some(grid_spot) = grid.get(current_pos.1, current_pos.0) {
+    while let Some(grid_spot) = grid.get(current_pos.0, current_pos.1) {
         if let GridSpot::Num(_) = grid_spot {
-            let surrounding = surrounding_indexes(current_pos, grid.size());
-            let has_symbol = surrounding.iter().any(|x| {
-                let grid_spot = grid.get(x.1, x.0).unwrap();
-                grid_spot.is_symbol()
-            });
+            let has_symbol = grid
+                .neighbours(Point::from(current_pos), Topology::Moore)
+                .any(|neighbour| grid[neighbour.point].is_symbol());
             if has_symbol {
                 return true;
             }
@@ -112,7 +103,7 @@ fn find_numbers_in_line(grid: &Grid<GridSpot>, line_num: usize) -> Vec<(usize, u
 
 pub fn find_number_starts(grid: &Grid<GridSpot>) -> Vec<(usize, usize)> {
     let mut vec = Vec::new();
-    for y in 0..grid.size().1 {
+    for y in 0..grid.height() {
         vec.append(&mut find_numbers_in_line(grid, y));
     }
     vec
@@ -132,19 +123,15 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(resolved_numbers.iter().sum())
 }
 
-pub fn potential_gear(grid: &Grid<GridSpot>, pos: (usize, usize)) -> Option<(usize, usize)> {
+pub fn potential_gear(grid: &Grid<GridSpot>, pos: (usize, usize)) -> Option<Point> {
     let mut current_pos = pos;
-    while let Some(grid_spot) = grid.get(current_pos.1, current_pos.0) {
+    while let Some(grid_spot) = grid.get(current_pos.0, current_pos.1) {
         if let GridSpot::Num(_) = grid_spot {
-            let surrounding = surrounding_indexes(current_pos, grid.size());
-            let mut gear_pos = (0, 0);
-            let has_gear = surrounding.iter().any(|x| {
-                let grid_spot = grid.get(x.1, x.0).unwrap();
-                gear_pos = *x;
-                matches!(grid_spot, GridSpot::Gear)
-            });
-            if has_gear {
-                return Some(gear_pos);
+            let gear = grid
+                .neighbours(Point::from(current_pos), Topology::Moore)
+                .find(|neighbour| matches!(grid[neighbour.point], GridSpot::Gear));
+            if let Some(gear) = gear {
+                return Some(gear.point);
             }
             current_pos.0 += 1;
         } else {
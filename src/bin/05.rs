@@ -1,117 +1,338 @@
-use std::collections::{BTreeMap, HashMap};
+use advent_of_code::math::Unsigned;
+use advent_of_code::ranges::{RangeMap, RangeSet, RangeValue};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::error::Error;
+use std::fmt::{Debug, Display};
 use std::iter::Map;
+use std::ops::{Range, RangeInclusive};
 use std::str::FromStr;
 advent_of_code::solution!(5);
 
+/// The integer types an almanac can be parsed into.
+///
+/// Ranges of numbers are handed out as inclusive ranges of `Self`, but held internally as
+/// half-open ranges of the next wider type so that they can end one past `Self::MAX`.
+/// That means `u128` can't be used, as there's nothing wider.
+pub trait Number:
+    Unsigned + RangeValue + FromStr + Debug + Into<Self::Wide> + TryFrom<Self::Wide>
+{
+    type Wide: Unsigned + RangeValue + Debug;
+}
+
+macro_rules! impl_number {
+    ($($t:ty => $wide:ty),*) => {
+        $(
+            impl Number for $t {
+                type Wide = $wide;
+            }
+        )*
+    };
+}
+
+impl_number!(u8 => u16, u16 => u32, u32 => u64, u64 => u128);
+
+/// Why an almanac couldn't be parsed.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Mapping {
-    pub dest: (u32, u32),
-    pub source: (u32, u32),
-    pub range: u32,
+pub enum AlmanacError<T: Number> {
+    /// A line that doesn't have the expected shape.
+    Malformed(String),
+    /// A number that isn't valid for the integer type, e.g. because it's too large.
+    InvalidNumber(String),
+    /// A range whose last number doesn't fit in the integer type.
+    Overflow { start: T, length: T },
+    /// A range covering no numbers at all.
+    ZeroLength { start: T },
+    /// Two source ranges in the same map that share numbers.
+    Overlap {
+        existing: RangeInclusive<T>,
+        inserted: RangeInclusive<T>,
+    },
 }
 
-impl Mapping {
-    pub fn map(&self, number: u32) -> Option<u32> {
-        if number >= self.source.0 && number <= self.source.1 {
-            let offset = number - self.source.0;
-            Some(self.dest.0 + offset)
-        } else {
-            None
+impl<T: Number> Error for AlmanacError<T> {}
+
+impl<T: Number> Display for AlmanacError<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AlmanacError::Malformed(line) => write!(f, "malformed line {line:?}"),
+            AlmanacError::InvalidNumber(number) => write!(f, "invalid number {number:?}"),
+            AlmanacError::Overflow { start, length } => {
+                write!(f, "range of {length:?} from {start:?} overflows")
+            }
+            AlmanacError::ZeroLength { start } => write!(f, "empty range at {start:?}"),
+            AlmanacError::Overlap { existing, inserted } => {
+                write!(
+                    f,
+                    "source range {inserted:?} overlaps existing range {existing:?}"
+                )
+            }
         }
     }
 }
 
-impl FromStr for Mapping {
-    type Err = ();
+fn parse_number<T: Number>(s: &str) -> Result<T, AlmanacError<T>> {
+    s.parse()
+        .map_err(|_| AlmanacError::InvalidNumber(s.to_string()))
+}
+
+/// The last of the `length` numbers from `start`.
+fn checked_last<T: Number>(start: T, length: T) -> Result<T, AlmanacError<T>> {
+    if length == T::ZERO {
+        return Err(AlmanacError::ZeroLength { start });
+    }
+    start
+        .checked_add(length - T::ONE)
+        .ok_or(AlmanacError::Overflow { start, length })
+}
+
+/// `range` as a half-open range of the wider type, which may end one past `T::MAX`.
+fn widen<T: Number>(range: &RangeInclusive<T>) -> Range<T::Wide> {
+    (*range.start()).into()..(*range.end()).into() + T::Wide::ONE
+}
+
+/// Converts a number that came from the ranges back to `T`. Every range is made of
+/// numbers that fit in `T`, so this can't fail.
+fn narrow<T: Number>(wide: T::Wide) -> T {
+    T::try_from(wide).unwrap_or_else(|_| panic!("{wide:?} is out of range"))
+}
+
+/// The inverse of [`widen`], for a non-empty range.
+fn narrow_range<T: Number>(range: &Range<T::Wide>) -> RangeInclusive<T> {
+    narrow(range.start)..=narrow(range.end - T::Wide::ONE)
+}
+
+/// Collects inclusive ranges into a set of wide, half-open ones.
+fn to_set<T: Number>(numbers: &[RangeInclusive<T>]) -> RangeSet<T::Wide> {
+    numbers.iter().map(widen).collect()
+}
+
+/// The ranges of a wide set as inclusive ranges of `T`, in ascending order.
+fn from_set<T: Number>(set: &RangeSet<T::Wide>) -> Vec<RangeInclusive<T>> {
+    set.iter().map(narrow_range).collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mapping<T = u64> {
+    pub dest: (T, T),
+    pub source: (T, T),
+    pub range: T,
+}
+
+impl<T: Number> FromStr for Mapping<T> {
+    type Err = AlmanacError<T>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let split: Vec<_> = s.split_whitespace().collect();
         if split.len() != 3 {
-            return Err(());
+            return Err(AlmanacError::Malformed(s.to_string()));
         }
-        let dest = split[0].parse().unwrap();
-        let source = split[1].parse().unwrap();
-        let range = split[2].parse().unwrap();
-
+        let dest = parse_number(split[0])?;
+        let source = parse_number(split[1])?;
+        let range = parse_number(split[2])?;
         Ok(Mapping {
-            dest: (dest, dest + (range - 1)),
-            source: (source, source + (range - 1)),
+            dest: (dest, checked_last(dest, range)?),
+            source: (source, checked_last(source, range)?),
             range,
         })
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Mappings {
+pub struct Mappings<T: Number = u64> {
     pub from: String,
     pub to: String,
-    pub mappings: Vec<Mapping>,
+    ranges: RangeMap<T::Wide>,
 }
 
-impl Mappings {
-    pub fn map(&self, number: u32) -> u32 {
-        let mut number = number;
-        for mapping in &self.mappings {
-            if let Some(mapped) = mapping.map(number) {
-                number = mapped;
-                break;
-            }
-        }
-        number
+impl<T: Number> Mappings<T> {
+    pub fn map(&self, number: T) -> T {
+        narrow(self.ranges.map(number.into()))
+    }
+
+    /// Maps every number in `numbers` at once, a range at a time. The result is sorted,
+    /// with overlapping and touching ranges merged.
+    pub fn map_ranges(&self, numbers: &[RangeInclusive<T>]) -> Vec<RangeInclusive<T>> {
+        from_set(&self.ranges.map_set(&to_set(numbers)))
     }
 }
 
-impl FromStr for Mappings {
-    type Err = ();
+impl<T: Number> FromStr for Mappings<T> {
+    type Err = AlmanacError<T>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut lines = s.lines();
-        let first_line = lines
-            .next()
-            .unwrap()
+        let first_line = lines.next().unwrap_or_default();
+        let (from, to) = first_line
             .strip_suffix(" map:")
-            .unwrap()
-            .split("-to-");
-        let from = first_line.clone().next().unwrap().to_string();
-        let to = first_line.clone().last().unwrap().to_string();
+            .and_then(|name| name.split_once("-to-"))
+            .ok_or_else(|| AlmanacError::Malformed(first_line.to_string()))?;
 
-        let mut mappings = vec![];
+        let mut ranges = RangeMap::new();
         for line in lines {
-            let mapping = line.parse::<Mapping>().unwrap();
-            mappings.push(mapping);
+            let mapping = line.parse::<Mapping<T>>()?;
+            ranges
+                .insert(
+                    widen(&(mapping.source.0..=mapping.source.1)),
+                    mapping.dest.0.into(),
+                )
+                .map_err(|overlap| AlmanacError::Overlap {
+                    existing: narrow_range(&overlap.existing),
+                    inserted: narrow_range(&overlap.inserted),
+                })?;
+        }
+        Ok(Mappings {
+            from: from.to_string(),
+            to: to.to_string(),
+            ranges,
+        })
+    }
+}
+
+/// The seeds and every map, as written in the almanac.
+pub type Almanac<T> = (Vec<T>, Vec<Mappings<T>>);
+
+/// Parses the list of seeds and every map.
+pub fn parse_almanac<T: Number>(input: &str) -> Result<Almanac<T>, AlmanacError<T>> {
+    let mut groups = input.split("\n\n");
+    let seed_line = groups.next().unwrap_or_default();
+    let seeds = seed_line
+        .strip_prefix("seeds: ")
+        .ok_or_else(|| AlmanacError::Malformed(seed_line.to_string()))?
+        .split_whitespace()
+        .map(parse_number)
+        .collect::<Result<_, _>>()?;
+    let mappings = groups
+        .filter(|group| !group.trim().is_empty())
+        .map(str::parse)
+        .collect::<Result<_, _>>()?;
+    Ok((seeds, mappings))
+}
+
+/// Why a chain of maps between two categories couldn't be resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainError {
+    /// Two maps convert from the same category, so the chain would be ambiguous.
+    DuplicateSource(String),
+    /// Following the maps from this category leads back to it.
+    Cycle(String),
+    /// There is no chain of maps leading from `from` to `to`.
+    MissingLink { from: String, to: String },
+}
+
+impl Error for ChainError {}
+
+impl Display for ChainError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChainError::DuplicateSource(category) => {
+                write!(f, "more than one map converts from {category}")
+            }
+            ChainError::Cycle(category) => write!(f, "the maps from {category} form a cycle"),
+            ChainError::MissingLink { from, to } => {
+                write!(f, "no chain of maps from {from} to {to}")
+            }
         }
-        Ok(Mappings { from, to, mappings })
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct MasterMap {
-    pub maps: HashMap<String, Mappings>,
+pub struct MasterMap<T: Number = u64> {
+    /// Every map, keyed by the category it converts from.
+    pub maps: HashMap<String, Mappings<T>>,
 }
 
-impl MasterMap {
-    pub fn new(in_vec: &[Mappings]) -> Self {
+impl<T: Number> MasterMap<T> {
+    /// Indexes the maps by the category they convert from. Fails if two maps share a
+    /// source category or the maps loop back on themselves.
+    pub fn new(in_vec: &[Mappings<T>]) -> Result<Self, ChainError> {
         let mut maps = HashMap::new();
         for mapping in in_vec {
-            maps.insert(mapping.to.clone(), mapping.clone());
+            if maps.insert(mapping.from.clone(), mapping.clone()).is_some() {
+                return Err(ChainError::DuplicateSource(mapping.from.clone()));
+            }
+        }
+        let master_map = MasterMap { maps };
+        for start in master_map.maps.keys() {
+            let mut seen = HashSet::new();
+            let mut category = start.as_str();
+            while let Some(mappings) = master_map.maps.get(category) {
+                if !seen.insert(category) {
+                    return Err(ChainError::Cycle(start.clone()));
+                }
+                category = &mappings.to;
+            }
         }
-        MasterMap { maps }
+        Ok(master_map)
     }
 
-    pub fn map(&self, to: &str, number: u32) -> u32 {
-        let mappings = self.maps.get(to).unwrap();
-        mappings.map(number)
+    /// The maps to apply, in order, to convert from `from` to `to`.
+    pub fn chain(&self, from: &str, to: &str) -> Result<Vec<&Mappings<T>>, ChainError> {
+        let mut chain = vec![];
+        let mut category = from;
+        while category != to {
+            let mappings = self
+                .maps
+                .get(category)
+                .ok_or_else(|| ChainError::MissingLink {
+                    from: from.to_string(),
+                    to: to.to_string(),
+                })?;
+            chain.push(mappings);
+            category = &mappings.to;
+        }
+        Ok(chain)
     }
 
-    pub fn map_all(&self, number: u32) -> Results {
+    pub fn map_between(&self, from: &str, to: &str, number: T) -> Result<T, ChainError> {
+        let chain = self.chain(from, to)?;
+        Ok(chain
+            .iter()
+            .fold(number, |number, mappings| mappings.map(number)))
+    }
+
+    /// Maps whole ranges of numbers at once. The result is sorted, with overlapping and
+    /// touching ranges merged.
+    pub fn map_ranges_between(
+        &self,
+        from: &str,
+        to: &str,
+        numbers: &[RangeInclusive<T>],
+    ) -> Result<Vec<RangeInclusive<T>>, ChainError> {
+        let chain = self.chain(from, to)?;
+        let mapped = chain.iter().fold(to_set(numbers), |numbers, mappings| {
+            mappings.ranges.map_set(&numbers)
+        });
+        Ok(from_set(&mapped))
+    }
+
+    /// Every number in category `from` that ends up in `numbers` in category `to`, e.g.
+    /// the seeds that lead to a given location.
+    pub fn unmap_between(
+        &self,
+        from: &str,
+        to: &str,
+        numbers: &[RangeInclusive<T>],
+    ) -> Result<Vec<RangeInclusive<T>>, ChainError> {
+        let chain = self.chain(from, to)?;
+        let unmapped = chain
+            .iter()
+            .rev()
+            .fold(to_set(numbers), |numbers, mappings| {
+                mappings.ranges.preimage(&numbers)
+            });
+        Ok(from_set(&unmapped))
+    }
+
+    pub fn map_all(&self, number: T) -> Results<T> {
+        let step = |from, to, number| self.map_between(from, to, number).unwrap();
         let seed = number;
-        let soil = self.map("soil", seed);
-        let fertilizer = self.map("fertilizer", soil);
-        let water = self.map("water", fertilizer);
-        let light = self.map("light", water);
-        let temperature = self.map("temperature", light);
-        let humidity = self.map("humidity", temperature);
-        let location = self.map("location", humidity);
+        let soil = step("seed", "soil", seed);
+        let fertilizer = step("soil", "fertilizer", soil);
+        let water = step("fertilizer", "water", fertilizer);
+        let light = step("water", "light", water);
+        let temperature = step("light", "temperature", light);
+        let humidity = step("temperature", "humidity", temperature);
+        let location = step("humidity", "location", humidity);
         Results {
             seed,
             soil,
@@ -126,88 +347,50 @@ impl MasterMap {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Results {
-    pub seed: u32,
-    pub soil: u32,
-    pub fertilizer: u32,
-    pub water: u32,
-    pub light: u32,
-    pub temperature: u32,
-    pub humidity: u32,
-    pub location: u32,
+pub struct Results<T = u64> {
+    pub seed: T,
+    pub soil: T,
+    pub fertilizer: T,
+    pub water: T,
+    pub light: T,
+    pub temperature: T,
+    pub humidity: T,
+    pub location: T,
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let mut groups = input.split("\n\n");
-    let seeds = groups
-        .next()
-        .unwrap()
-        .strip_prefix("seeds: ")
-        .unwrap()
-        .split_whitespace()
-        .map(|s| s.parse::<u32>().unwrap())
-        .collect::<Vec<_>>();
-    let mappings: Vec<_> = groups.map(|g| g.parse::<Mappings>().unwrap()).collect();
-    let master_map = MasterMap::new(&mappings);
-    println!("{master_map:#?}");
+/// Reads the seeds as `start length` pairs. The result is sorted, with overlapping and
+/// touching ranges merged.
+pub fn seed_ranges<T: Number>(seeds: &[T]) -> Result<Vec<RangeInclusive<T>>, AlmanacError<T>> {
+    if !seeds.len().is_multiple_of(2) {
+        return Err(AlmanacError::Malformed(format!("{seeds:?}")));
+    }
+    let ranges = seeds
+        .chunks(2)
+        .map(|pair| Ok(pair[0]..=checked_last(pair[0], pair[1])?))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(from_set(&to_set(&ranges)))
+}
 
-    let results = seeds
-        .iter()
-        .map(|seed| master_map.map_all(*seed))
-        .collect::<Vec<_>>();
+pub fn part_one(input: &str) -> Option<u64> {
+    let (seeds, mappings) = parse_almanac::<u64>(input).unwrap();
+    let master_map = MasterMap::new(&mappings).unwrap();
 
-    let min = results.iter().map(|r| r.location).min().unwrap();
-    Some(min)
+    seeds
+        .iter()
+        .map(|&seed| master_map.map_between("seed", "location", seed).unwrap())
+        .min()
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let mut groups = input.split("\n\n");
-
-    /*
-    let seeds = groups
-        .next()
-        .unwrap()
-        .strip_prefix("seeds: ")
-        .unwrap()
-        .split_whitespace()
-        .map(|s| s.parse::<u32>().unwrap())
-        .collect::<Vec<_>>();
-     */
+pub fn part_two(input: &str) -> Option<u64> {
+    let (seeds, mappings) = parse_almanac::<u64>(input).unwrap();
+    let seed_ranges = seed_ranges(&seeds).unwrap();
+    let master_map = MasterMap::new(&mappings).unwrap();
 
-    let seeds: Vec<_> = groups
-        .next()
+    master_map
+        .map_ranges_between("seed", "location", &seed_ranges)
         .unwrap()
-        .strip_prefix("seeds: ")
-        .unwrap()
-        .split_whitespace()
-        .collect();
-    println!("Seed Ranges (pre parse): {:#?}", seeds);
-
-    let seeds_ranges: Vec<_> = seeds
-        .chunks(2)
-        .map(|window| {
-            let low = window[0].parse::<u32>().unwrap();
-            let high = low + (window[1].parse::<u32>().unwrap() - 1);
-            (low, high)
-        })
-        .collect();
-    println!("Seed Ranges: {:#?}", seeds_ranges);
-
-    let mappings: Vec<_> = groups.map(|g| g.parse::<Mappings>().unwrap()).collect();
-    let master_map = MasterMap::new(&mappings);
-
-    let mut lowest_so_far = u32::MAX;
-    for (low, high) in seeds_ranges {
-        println!("Checkpoint! Starting {} - {}", low, high);
-        for i in low..=high {
-            let results = master_map.map_all(i);
-            if results.location < lowest_so_far {
-                println!("Found new lowest: {}", results.location);
-                lowest_so_far = results.location;
-            }
-        }
-    }
-    Some(lowest_so_far)
+        .first()
+        .map(|range| *range.start())
 }
 
 #[cfg(test)]
@@ -224,11 +407,10 @@ mod tests {
             .strip_prefix("seeds: ")
             .unwrap()
             .split_whitespace()
-            .map(|s| s.parse::<u32>().unwrap())
+            .map(|s| s.parse::<u64>().unwrap())
             .collect::<Vec<_>>();
         let mappings: Vec<_> = groups.map(|g| g.parse::<Mappings>().unwrap()).collect();
-        let master_map = MasterMap::new(&mappings);
-        println!("{master_map:#?}");
+        let master_map = MasterMap::new(&mappings).unwrap();
 
         let result = master_map.map_all(14);
 
@@ -246,6 +428,127 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn resolves_chains_both_ways() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        let mappings: Vec<_> = input
+            .split("\n\n")
+            .skip(1)
+            .map(|g| g.parse::<Mappings>().unwrap())
+            .collect();
+        let master_map = MasterMap::new(&mappings).unwrap();
+        assert_eq!(master_map.map_between("soil", "water", 14), Ok(49));
+        assert_eq!(master_map.map_between("seed", "seed", 14), Ok(14));
+        assert_eq!(
+            master_map.map_between("location", "seed", 43),
+            Err(ChainError::MissingLink {
+                from: "location".to_string(),
+                to: "seed".to_string()
+            })
+        );
+
+        // searching from the answer side: the seeds that end up at location 46
+        let seeds = master_map
+            .unmap_between("seed", "location", &[46..=46])
+            .unwrap();
+        assert!(seeds.iter().any(|range| range.contains(&82)));
+        for range in seeds.iter() {
+            assert_eq!(
+                master_map.map_between("seed", "location", *range.start()),
+                Ok(46)
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_cycles_and_duplicates() {
+        let a: Mappings = "a-to-b map:\n1 2 3".parse().unwrap();
+        let b: Mappings = "b-to-a map:\n1 2 3".parse().unwrap();
+        // either category can be reported depending on iteration order
+        assert!(matches!(
+            MasterMap::new(&[a.clone(), b]),
+            Err(ChainError::Cycle(_))
+        ));
+        assert_eq!(
+            MasterMap::new(&[a.clone(), a]),
+            Err(ChainError::DuplicateSource("a".to_string()))
+        );
+    }
+
+    #[test]
+    fn reports_typed_parse_errors() {
+        assert_eq!(
+            "1 2".parse::<Mapping>(),
+            Err(AlmanacError::Malformed("1 2".to_string()))
+        );
+        assert_eq!(
+            "50 98 0".parse::<Mapping>(),
+            Err(AlmanacError::ZeroLength { start: 50 })
+        );
+        assert_eq!(
+            "4294967295 0 2".parse::<Mapping<u32>>(),
+            Err(AlmanacError::Overflow {
+                start: u32::MAX,
+                length: 2
+            })
+        );
+        // fine once the numbers are 64-bit
+        assert!("4294967295 0 2".parse::<Mapping>().is_ok());
+        assert_eq!(
+            "4294967295 0 1".parse::<Mapping<u32>>(),
+            Ok(Mapping {
+                dest: (u32::MAX, u32::MAX),
+                source: (0, 0),
+                range: 1
+            })
+        );
+        assert_eq!(
+            "1 2 x".parse::<Mapping>(),
+            Err(AlmanacError::InvalidNumber("x".to_string()))
+        );
+        assert_eq!(
+            "a-to-b map:\n0 10 5\n20 12 5".parse::<Mappings>(),
+            Err(AlmanacError::Overlap {
+                existing: 10..=14,
+                inserted: 12..=16
+            })
+        );
+        assert!(matches!(
+            parse_almanac::<u64>("seed: 1 2\n\na-to-b map:\n0 10 5"),
+            Err(AlmanacError::Malformed(_))
+        ));
+        assert!(matches!(
+            seed_ranges(&[79u64, 14, 55]),
+            Err(AlmanacError::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn ranges_can_end_at_the_largest_number() {
+        let mappings: Mappings<u32> = "a-to-b map:\n0 4294967294 2\n4294967294 0 1"
+            .parse()
+            .unwrap();
+        assert_eq!(mappings.map(u32::MAX - 1), 0);
+        assert_eq!(mappings.map(u32::MAX), 1);
+        assert_eq!(mappings.map(0), u32::MAX - 1);
+
+        let seeds = seed_ranges(&[u32::MAX - 2, 3]).unwrap();
+        assert_eq!(seeds, [u32::MAX - 2..=u32::MAX]);
+        assert_eq!(
+            mappings.map_ranges(&seeds),
+            [0..=1, u32::MAX - 2..=u32::MAX - 2]
+        );
+        // overlapping and touching ranges are merged
+        assert_eq!(seed_ranges(&[5u32, 3, 0, 5, 9, 1]).unwrap(), [0..=7, 9..=9]);
+        assert_eq!(
+            seed_ranges(&[u32::MAX, 2]),
+            Err(AlmanacError::Overflow {
+                start: u32::MAX,
+                length: 2
+            })
+        );
+    }
+
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
@@ -1,16 +1,47 @@
-use std::str::FromStr;
+use advent_of_code::math::isqrt;
 advent_of_code::solution!(6);
-use rayon::prelude::*;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Race {
-    pub length: u32,
-    pub record: u32,
+    pub length: u64,
+    pub record: u64,
+}
+
+impl Race {
+    /// Whether holding the button for `hold` milliseconds beats the record.
+    pub fn wins(&self, hold: u64) -> bool {
+        hold <= self.length && hold as u128 * (self.length - hold) as u128 > self.record as u128
+    }
+
+    /// The number of hold times that beat the record. Holding for `x` travels
+    /// `x·(length - x)`, so the winners are the integers strictly between the roots of
+    /// `x² - length·x + record`.
+    pub fn ways_to_win(&self) -> u64 {
+        let length = self.length as u128;
+        let discriminant = match (length * length).checked_sub(4 * self.record as u128) {
+            Some(discriminant) => discriminant,
+            None => return 0,
+        };
+        // the lower root estimated with an integer square root can be off by one either
+        // way, so nudge it onto the first winning hold time
+        let mut low = ((length - isqrt(discriminant)) / 2) as u64;
+        while low > 0 && self.wins(low - 1) {
+            low -= 1;
+        }
+        while low <= self.length / 2 && !self.wins(low) {
+            low += 1;
+        }
+        if low > self.length / 2 {
+            return 0;
+        }
+        // the distances are symmetric, so holding for `length - low` is the last winner
+        self.length - 2 * low + 1
+    }
 }
 
 pub fn parse_races(in_str: &str) -> Vec<Race> {
     let mut lines = in_str.lines();
-    let times: Vec<u32> = lines
+    let times: Vec<u64> = lines
         .next()
         .unwrap()
         .strip_prefix("Time:")
@@ -18,7 +49,7 @@ pub fn parse_races(in_str: &str) -> Vec<Race> {
         .split_whitespace()
         .map(|s| s.parse().unwrap())
         .collect();
-    let records: Vec<u32> = lines
+    let records: Vec<u64> = lines
         .next()
         .unwrap()
         .strip_prefix("Distance:")
@@ -37,32 +68,14 @@ pub fn parse_races(in_str: &str) -> Vec<Race> {
         .collect()
 }
 
-fn brute_force_race(race: Race) -> usize {
-    let record = race.record;
-    let length = race.length;
-    (1..length)
-        .into_par_iter()
-        .map(|x| {
-            let running_time = length - x;
-            running_time * x
-        })
-        .filter(|&x| x > record)
-        .count()
-}
-
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(input: &str) -> Option<u64> {
     let races = parse_races(input);
-    let mut records = Vec::new();
-    for race in races {
-        let winning_records = brute_force_race(race);
-        records.push(winning_records as u32);
-    }
-    Some(records.iter().product())
+    Some(races.iter().map(Race::ways_to_win).product())
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
     let mut lines = input.lines();
-    let time: u64 = lines
+    let length = lines
         .next()
         .unwrap()
         .replace(' ', "")
@@ -70,26 +83,60 @@ pub fn part_two(input: &str) -> Option<u64> {
         .unwrap()
         .parse()
         .unwrap();
-    let distance = lines
+    let record = lines
         .next()
         .unwrap()
         .replace(' ', "")
         .strip_prefix("Distance:")
         .unwrap()
-        .parse::<u64>()
+        .parse()
         .unwrap();
 
-    let winning_records = brute_force_race(Race {
-        length: time as u32,
-        record: distance as u32,
-    });
-    Some(winning_records as u64)
+    Some(Race { length, record }.ways_to_win())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn brute_force_race(race: Race) -> u64 {
+        (0..=race.length).filter(|&hold| race.wins(hold)).count() as u64
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        for length in 0..60 {
+            for record in 0..(length * length / 4 + 2) {
+                let race = Race { length, record };
+                assert_eq!(race.ways_to_win(), brute_force_race(race), "{race:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn handles_huge_races() {
+        // holding for k ± d travels k² - d², so exactly 2m - 1 hold times beat k² - m²
+        let k = 3_000_000_000;
+        let m = 12_345;
+        let race = Race {
+            length: 2 * k,
+            record: k * k - m * m,
+        };
+        assert_eq!(race.ways_to_win(), 2 * m - 1);
+        let race = Race {
+            length: 2 * k,
+            record: k * k,
+        };
+        assert_eq!(race.ways_to_win(), 0);
+
+        // the best distance here doesn't fit in a u64
+        let race = Race {
+            length: u64::MAX,
+            record: 0,
+        };
+        assert_eq!(race.ways_to_win(), u64::MAX - 1);
+    }
+
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
@@ -1,24 +1,10 @@
+use advent_of_code::math::lcm_all;
+use advent_of_code::Turn;
 use rayon::prelude::*;
 use std::collections::{BTreeMap, HashSet};
 use std::str::FromStr;
 advent_of_code::solution!(8);
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum Direction {
-    Left,
-    Right,
-}
-
-impl From<char> for Direction {
-    fn from(c: char) -> Self {
-        match c {
-            'L' => Direction::Left,
-            'R' => Direction::Right,
-            _ => panic!("Invalid direction: {}", c),
-        }
-    }
-}
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
 pub struct Coordinate(pub [char; 3]);
 
@@ -54,10 +40,10 @@ pub struct Instruction {
 }
 
 impl Instruction {
-    pub fn pick(&self, direction: Direction) -> Coordinate {
-        match direction {
-            Direction::Left => self.left,
-            Direction::Right => self.right,
+    pub fn pick(&self, turn: Turn) -> Coordinate {
+        match turn {
+            Turn::Left => self.left,
+            Turn::Right => self.right,
         }
     }
 }
@@ -81,7 +67,7 @@ impl FromStr for Instruction {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Map {
-    pub sequence: Vec<Direction>,
+    pub sequence: Vec<Turn>,
     pub coordinates: BTreeMap<Coordinate, Instruction>,
 }
 
@@ -89,10 +75,10 @@ impl Map {
     pub fn steps_to_zzz(&self) -> usize {
         let mut steps = 0;
         let mut current = "AAA".parse::<Coordinate>().unwrap();
-        for direction in self.sequence.iter().cycle() {
+        for turn in self.sequence.iter().cycle() {
             steps += 1;
             let instruction = self.coordinates.get(&current).unwrap();
-            current = instruction.pick(*direction);
+            current = instruction.pick(*turn);
             if current == "ZZZ".parse().unwrap() {
                 break;
             }
@@ -113,10 +99,10 @@ impl Map {
     pub fn length_to_z(&self, starting_node: Coordinate) -> usize {
         let mut steps = 0;
         let mut current = starting_node;
-        for direction in self.sequence.iter().cycle() {
+        for turn in self.sequence.iter().cycle() {
             steps += 1;
             let instruction = self.coordinates.get(&current).unwrap();
-            current = instruction.pick(*direction);
+            current = instruction.pick(*turn);
             if current.last() == 'Z' {
                 break;
             }
@@ -124,14 +110,15 @@ impl Map {
         steps
     }
 
-    pub fn steps_to_all_z(&self) -> usize {
+    /// Assumes each ghost's path loops back to its start right after reaching a `Z` node,
+    /// so that all of them are on a `Z` node after the lcm of their path lengths.
+    pub fn steps_to_all_z(&self) -> Option<u64> {
         let starting_nodes = self.starting_nodes();
         let steps_array = starting_nodes
             .par_iter()
-            .map(|&starting_node| self.length_to_z(starting_node))
+            .map(|&starting_node| self.length_to_z(starting_node) as u64)
             .collect::<Vec<_>>();
-        println!("steps_array: {:?}", steps_array);
-        lcm_slice(&steps_array)
+        lcm_all(steps_array)
     }
 }
 
@@ -144,8 +131,9 @@ impl FromStr for Map {
             .next()
             .unwrap()
             .chars()
-            .map(Direction::from)
-            .collect::<Vec<_>>();
+            .map(Turn::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| ())?;
 
         let coordinates = lines
             .filter(|x| !x.is_empty())
@@ -164,42 +152,15 @@ impl FromStr for Map {
     }
 }
 
-/// calculate the greatest common divisor of two numbers using the euclidean algorithm
-pub fn greatest_common_divisor(mut a: usize, mut b: usize) -> usize {
-    while b != 0 {
-        if b < a {
-            std::mem::swap(&mut b, &mut a);
-        }
-        b %= a;
-    }
-    a
-}
-
-pub fn least_common_multiple(a: usize, b: usize) -> usize {
-    (a * b) / greatest_common_divisor(a, b)
-}
-
-// this doesn't work and I'm not sure why, I just ended up using a calculator on the intermediate result
-pub fn lcm_slice(slice: &[usize]) -> usize {
-    if slice.len() == 1 {
-        slice[0]
-    } else if slice.len() == 2 {
-        least_common_multiple(slice[0], slice[1])
-    } else {
-        least_common_multiple(slice[0], lcm_slice(&slice[1..]))
-    }
-}
-
 pub fn part_one(input: &str) -> Option<u32> {
     let map = input.parse::<Map>().unwrap();
     let steps = map.steps_to_zzz();
     Some(steps as u32)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str) -> Option<u64> {
     let map = input.parse::<Map>().unwrap();
-    let steps = map.steps_to_all_z();
-    Some(steps as u32)
+    map.steps_to_all_z()
 }
 
 #[cfg(test)]
@@ -215,7 +176,6 @@ mod tests {
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        println!("result: {:?}", lcm_slice(&[27, 43, 81]));
         assert_eq!(result, Some(6));
     }
 }
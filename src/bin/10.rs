@@ -1,6 +1,8 @@
+use advent_of_code::geometry::LoopMetrics;
+use advent_of_code::pathfinding::bfs;
+use advent_of_code::render::GridRenderer;
 use advent_of_code::template::aoc_cli::check;
-use advent_of_code::{Direction, Grid};
-use std::collections::HashSet;
+use advent_of_code::{Direction, Grid, GridParseError, Point};
 use std::fmt::Display;
 use std::str::FromStr;
 advent_of_code::solution!(10);
@@ -16,25 +18,27 @@ pub enum Pipe {
     Start,
     #[default]
     Ground,
-    INNER,
-    OUTER,
 }
 
-impl Pipe {
-    pub fn from_char(c: char) -> Option<Pipe> {
+impl TryFrom<char> for Pipe {
+    type Error = ();
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
         match c {
-            '|' => Some(Pipe::Vertical),
-            '-' => Some(Pipe::Horizontal),
-            'L' => Some(Pipe::NorthEast),
-            'J' => Some(Pipe::NorthWest),
-            '7' => Some(Pipe::SouthWest),
-            'F' => Some(Pipe::SouthEast),
-            'S' => Some(Pipe::Start),
-            '.' => Some(Pipe::Ground),
-            _ => None,
+            '|' => Ok(Pipe::Vertical),
+            '-' => Ok(Pipe::Horizontal),
+            'L' => Ok(Pipe::NorthEast),
+            'J' => Ok(Pipe::NorthWest),
+            '7' => Ok(Pipe::SouthWest),
+            'F' => Ok(Pipe::SouthEast),
+            'S' => Ok(Pipe::Start),
+            '.' => Ok(Pipe::Ground),
+            _ => Err(()),
         }
     }
+}
 
+impl Pipe {
     pub fn to_char(&self) -> char {
         match self {
             Pipe::Vertical => '│',
@@ -45,8 +49,6 @@ impl Pipe {
             Pipe::SouthEast => '┌',
             Pipe::Start => 'S',
             Pipe::Ground => ' ',
-            Pipe::INNER => 'I',
-            Pipe::OUTER => 'O',
         }
     }
 
@@ -59,23 +61,12 @@ impl Pipe {
     /// Note that the provided direction is the direction which was stepped towards.
     /// ie, if you are coming from the north you stepped south to get to the pipe being checked
     pub fn next_from_direction(self, direction: Direction) -> Option<Direction> {
-        match (self, direction) {
-            (Pipe::Vertical, Direction::South) => Some(Direction::South),
-            (Pipe::Vertical, Direction::North) => Some(Direction::North),
-            (Pipe::Horizontal, Direction::East) => Some(Direction::East),
-            (Pipe::Horizontal, Direction::West) => Some(Direction::West),
-            (Pipe::NorthEast, Direction::South) => Some(Direction::East),
-            (Pipe::NorthEast, Direction::West) => Some(Direction::North),
-            (Pipe::NorthWest, Direction::South) => Some(Direction::West),
-            (Pipe::NorthWest, Direction::East) => Some(Direction::North),
-            (Pipe::SouthWest, Direction::North) => Some(Direction::West),
-            (Pipe::SouthWest, Direction::East) => Some(Direction::South),
-            (Pipe::SouthEast, Direction::North) => Some(Direction::East),
-            (Pipe::SouthEast, Direction::West) => Some(Direction::South),
-            _ => {
-                panic!("Invalid direction {:?} for pipe {:?}", direction, self);
-            }
+        let entrance = direction.opposite();
+        let connected = self.get_connected_directions();
+        if self.is_start() || !connected.contains(&entrance) {
+            panic!("Invalid direction {:?} for pipe {:?}", direction, self);
         }
+        connected.into_iter().find(|&exit| exit != entrance)
     }
 
     pub fn get_connected_directions(self) -> Vec<Direction> {
@@ -86,16 +77,8 @@ impl Pipe {
             Pipe::NorthWest => vec![Direction::North, Direction::West],
             Pipe::SouthWest => vec![Direction::South, Direction::West],
             Pipe::SouthEast => vec![Direction::South, Direction::East],
-            Pipe::Start => vec![
-                Direction::North,
-                Direction::South,
-                Direction::East,
-                Direction::West,
-            ],
+            Pipe::Start => Direction::CARDINALS.to_vec(),
             Pipe::Ground => vec![],
-            _ => {
-                panic!("Invalid pipe {:?}", self);
-            }
         }
     }
 
@@ -103,25 +86,27 @@ impl Pipe {
     /// a pipe "coming from" that direction.
     /// ie, if the pipe is vertical, and the direction is south, return true.
     pub fn connected_to_from(self, direction: Direction) -> bool {
-        matches!(
-            (self, direction),
-            (Pipe::Vertical, Direction::South)
-                | (Pipe::Vertical, Direction::North)
-                | (Pipe::Horizontal, Direction::East)
-                | (Pipe::Horizontal, Direction::West)
-                | (Pipe::NorthEast, Direction::South)
-                | (Pipe::NorthEast, Direction::West)
-                | (Pipe::NorthWest, Direction::South)
-                | (Pipe::NorthWest, Direction::East)
-                | (Pipe::SouthWest, Direction::North)
-                | (Pipe::SouthWest, Direction::East)
-                | (Pipe::SouthEast, Direction::North)
-                | (Pipe::SouthEast, Direction::West)
-        )
+        !self.is_start()
+            && self
+                .get_connected_directions()
+                .contains(&direction.opposite())
     }
 
-    pub fn points_north(self) -> bool {
-        matches!(self, Pipe::Vertical | Pipe::NorthEast | Pipe::NorthWest)
+    /// The pipe that connects the two given directions.
+    pub fn from_connections(a: Direction, b: Direction) -> Option<Pipe> {
+        [
+            Pipe::Vertical,
+            Pipe::Horizontal,
+            Pipe::NorthEast,
+            Pipe::NorthWest,
+            Pipe::SouthWest,
+            Pipe::SouthEast,
+        ]
+        .into_iter()
+        .find(|pipe| {
+            let connected = pipe.get_connected_directions();
+            a != b && connected.contains(&a) && connected.contains(&b)
+        })
     }
 }
 
@@ -165,151 +150,89 @@ impl PipeGrid {
         [out[0], out[1]]
     }
 
+    /// Tiles that the pipe at `coords` connects to and that connect back to it.
+    fn connected_neighbours(&self, coords: (usize, usize)) -> Vec<(usize, usize)> {
+        let pipe = self.grid.get(coords.0, coords.1).unwrap();
+        pipe.get_connected_directions()
+            .into_iter()
+            .filter_map(|direction| {
+                let next = self.grid.get_step(direction, coords)?;
+                let next_pipe = self.grid.get(next.0, next.1)?;
+                next_pipe.connected_to_from(direction).then_some(next)
+            })
+            .collect()
+    }
+
     pub fn furthest_distance(&self) -> u32 {
-        let mut checked_coords = HashSet::new();
         let start = self.get_start_position().unwrap();
-        checked_coords.insert(start);
-        let mut current_check_coord = self.find_connected_to_start();
-        checked_coords.insert(current_check_coord[0].coordinates);
-        checked_coords.insert(current_check_coord[1].coordinates);
-        loop {
-            for step in current_check_coord.iter_mut() {
-                let next = step.pipe.next_from_direction(step.direction).unwrap();
-                let next_coord = self.grid.get_step(next, step.coordinates).unwrap();
-                if !checked_coords.contains(&next_coord) {
-                    checked_coords.insert(next_coord);
-                    let next_pipe = self.grid.get(next_coord.0, next_coord.1).unwrap();
-                    let next_distance = step.distance + 1;
-                    *step = PipeStep {
-                        pipe: *next_pipe,
-                        direction: next,
-                        coordinates: next_coord,
-                        distance: next_distance,
-                    };
-                } else {
-                    return step.distance + 1;
-                }
-            }
-        }
+        let result = bfs(
+            [Point::from(start)],
+            |point| {
+                let coords = point.to_grid_coords(self.grid.width(), self.grid.height());
+                self.connected_neighbours(coords.unwrap())
+                    .into_iter()
+                    .map(Point::from)
+            },
+            |_| false,
+        );
+        *result.distances().values().max().unwrap() as u32
+    }
+
+    /// The pipe hidden under the start tile, deduced from its connected neighbours.
+    pub fn start_pipe(&self) -> Pipe {
+        let [a, b] = self.find_connected_to_start();
+        Pipe::from_connections(a.direction, b.direction).unwrap()
     }
 
-    pub fn find_loop_tiles(&self) -> HashSet<(usize, usize)> {
-        let mut checked_coords = HashSet::new();
+    /// The tiles of the loop in the order they are walked, beginning at the start tile.
+    pub fn find_loop_tiles(&self) -> Vec<(usize, usize)> {
         let start = self.get_start_position().unwrap();
-        checked_coords.insert(start);
+        let mut tiles = vec![start];
         let mut step = self.find_connected_to_start()[0];
-        checked_coords.insert(step.coordinates);
-        loop {
+        while step.coordinates != start {
+            tiles.push(step.coordinates);
             let next = step.pipe.next_from_direction(step.direction).unwrap();
             let next_coord = self.grid.get_step(next, step.coordinates).unwrap();
-            if !checked_coords.contains(&next_coord) {
-                checked_coords.insert(next_coord);
-                let next_pipe = self.grid.get(next_coord.0, next_coord.1).unwrap();
-                let next_distance = step.distance + 1;
-                step = PipeStep {
-                    pipe: *next_pipe,
-                    direction: next,
-                    coordinates: next_coord,
-                    distance: next_distance,
-                };
-            } else {
-                return checked_coords;
-            }
-        }
-    }
-
-    pub fn grid_with_only_loop_tiles(&self) -> Self {
-        let mut grid = self.clone();
-        let loop_tiles = self.find_loop_tiles();
-        for (y, row) in self.grid.iter_rows().enumerate() {
-            for (x, _) in row.iter().enumerate() {
-                if !loop_tiles.contains(&(x, y)) {
-                    grid.grid.set(x, y, Pipe::Ground);
-                }
-            }
-        }
-        grid
-    }
-
-    pub fn grid_with_overlaid_inner_outer(&mut self) -> Self {
-        let mut grid = self.clone();
-        let loop_tiles = self.find_loop_tiles();
-        let enclosed_tiles = self.enclosed_tiles();
-        for (y, row) in self.grid.iter_rows().enumerate() {
-            for (x, _) in row.iter().enumerate() {
-                if !loop_tiles.contains(&(x, y)) {
-                    if enclosed_tiles.contains(&(x, y)) {
-                        grid.grid.set(x, y, Pipe::INNER);
-                    } else {
-                        grid.grid.set(x, y, Pipe::OUTER);
-                    }
-                }
-            }
+            let next_pipe = self.grid.get(next_coord.0, next_coord.1).unwrap();
+            step = PipeStep {
+                pipe: *next_pipe,
+                direction: next,
+                coordinates: next_coord,
+                distance: step.distance + 1,
+            };
         }
-        grid
+        tiles
     }
 
-    pub fn enclosed_tiles(&mut self) -> HashSet<(usize, usize)> {
-        let loop_tiles = self.find_loop_tiles();
-        // at this point I don't give a shit, this will only work on my input
-        if let Some(start) = self.get_start_position() {
-            self.grid.set(start.0, start.1, Pipe::NorthEast);
-        }
-        let mut inside = HashSet::new();
-        for (y, row) in self.grid.iter_rows().enumerate() {
-            let mut is_inside = false;
-            for (x, pipe) in row.iter().enumerate() {
-                if loop_tiles.contains(&(x, y)) {
-                    if pipe.points_north() {
-                        is_inside = !is_inside;
-                    }
-                } else if is_inside {
-                    inside.insert((x, y));
-                }
-            }
-        }
-        inside
+    /// The number of tiles enclosed by the loop, via the shoelace formula and Pick's theorem.
+    pub fn enclosed_count(&self) -> u32 {
+        let vertices: Vec<_> = self
+            .find_loop_tiles()
+            .into_iter()
+            .map(Point::from)
+            .collect();
+        LoopMetrics::from_vertices(&vertices).interior as u32
     }
 
     pub fn get_start_position(&self) -> Option<(usize, usize)> {
-        for (y, row) in self.grid.iter_rows().enumerate() {
-            for (x, pipe) in row.iter().enumerate() {
-                if pipe.is_start() {
-                    return Some((x, y));
-                }
-            }
-        }
-        None
+        let (width, height) = self.grid.dims();
+        self.grid
+            .position(|pipe| pipe.is_start())
+            .and_then(|point| point.to_grid_coords(width, height))
     }
 }
 
 impl Display for PipeGrid {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for row in self.grid.iter_rows() {
-            for pipe in row {
-                write!(f, "{}", pipe.to_char())?;
-            }
-            writeln!(f)?;
-        }
-        Ok(())
+        f.write_str(&GridRenderer::new(Pipe::to_char).render(&self.grid))
     }
 }
 
 impl FromStr for PipeGrid {
-    type Err = ();
+    type Err = GridParseError<()>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let lines = s.lines().collect::<Vec<&str>>();
-        let width = lines[0].len();
-        let height = lines.len();
-        let mut grid = Grid::new_filled_default(width, height);
-        for (y, line) in lines.iter().enumerate() {
-            for (x, c) in line.chars().enumerate() {
-                let pipe = Pipe::from_char(c).unwrap();
-                grid.set(x, y, pipe);
-            }
-        }
-        Ok(PipeGrid { grid })
+        Ok(PipeGrid { grid: s.parse()? })
     }
 }
 
@@ -322,13 +245,7 @@ pub fn part_one(input: &str) -> Option<u32> {
 
 pub fn part_two(input: &str) -> Option<u32> {
     let grid = PipeGrid::from_str(input).unwrap();
-    let loop_grid = grid.grid_with_only_loop_tiles();
-    println!("{loop_grid}");
-    let enclosed_tiles = grid.clone().enclosed_tiles();
-    println!("{:?}", enclosed_tiles);
-    let enclosed_grid = grid.clone().grid_with_overlaid_inner_outer();
-    println!("{enclosed_grid}");
-    Some(enclosed_tiles.len() as u32)
+    Some(grid.enclosed_count())
 }
 
 #[cfg(test)]
@@ -346,4 +263,10 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(1));
     }
+
+    #[test]
+    fn test_part_two_squeezed_loop() {
+        let input = advent_of_code::template::read_file_part("examples", DAY, 2);
+        assert_eq!(part_two(&input), Some(4));
+    }
 }
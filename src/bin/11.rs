@@ -1,12 +1,57 @@
-use grid::Grid;
+use advent_of_code::geometry::pairwise_manhattan_sum;
+use advent_of_code::{Compression, Grid};
 advent_of_code::solution!(11);
 
-pub fn part_one(input: &str) -> Option<u32> {
-    None
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Space {
+    #[default]
+    Empty,
+    Galaxy,
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    None
+impl TryFrom<char> for Space {
+    type Error = ();
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '.' => Ok(Space::Empty),
+            '#' => Ok(Space::Galaxy),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Sums the distances between every pair of galaxies after each empty row and column
+/// has grown to `expansion` times its size.
+pub fn sum_of_distances(input: &str, expansion: u64) -> u64 {
+    let grid: Grid<Space> = input.parse().unwrap();
+    let empty_rows: Vec<bool> = grid
+        .iter_rows()
+        .map(|row| row.iter().all(|&space| space == Space::Empty))
+        .collect();
+    let empty_columns: Vec<bool> = grid
+        .iter_cols()
+        .map(|mut column| column.all(|&space| space == Space::Empty))
+        .collect();
+    let weight = |empty: bool| if empty { expansion } else { 1 };
+    let compression = Compression::from_grid(
+        &grid,
+        |x| weight(empty_columns[x]),
+        |y| weight(empty_rows[y]),
+    );
+    let galaxies: Vec<_> = grid
+        .find_all(|&space| space == Space::Galaxy)
+        .map(|galaxy| compression.real(galaxy))
+        .collect();
+    pairwise_manhattan_sum(&galaxies)
+}
+
+pub fn part_one(input: &str) -> Option<u64> {
+    Some(sum_of_distances(input, 2))
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    Some(sum_of_distances(input, 1_000_000))
 }
 
 #[cfg(test)]
@@ -16,12 +61,19 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result, Some(374));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result, Some(82000210));
+    }
+
+    #[test]
+    fn test_smaller_expansions() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        assert_eq!(sum_of_distances(&input, 10), 1030);
+        assert_eq!(sum_of_distances(&input, 100), 8410);
     }
 }
@@ -0,0 +1,466 @@
+use crate::{Grid, Offset, Point};
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Index, Not};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A grid of booleans packed 64 cells to a word.
+///
+/// Each row starts on a new word, so whole rows can be combined and shifted a word at a
+/// time. Bits past the end of a row are always zero.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    words_per_row: usize,
+    data: Vec<u64>,
+}
+
+impl BitGrid {
+    /// A grid with every cell unset.
+    pub fn new(width: usize, height: usize) -> Self {
+        let words_per_row = width.div_ceil(WORD_BITS);
+        Self {
+            width,
+            height,
+            words_per_row,
+            data: vec![0; words_per_row * height],
+        }
+    }
+
+    /// Sets the cells of `grid` that match `predicate`.
+    pub fn from_grid<T: Clone>(grid: &Grid<T>, predicate: impl Fn(&T) -> bool) -> Self {
+        let mut bits = Self::new(grid.width, grid.height);
+        for (point, cell) in grid.cells() {
+            if predicate(cell) {
+                bits.set_point(point, true);
+            }
+        }
+        bits
+    }
+
+    /// A [`Grid`] with `f` applied to every cell.
+    pub fn to_grid_with<T: Clone>(&self, f: impl Fn(bool) -> T) -> Grid<T> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            data: self.cells().map(|(_, bit)| f(bit)).collect(),
+        }
+    }
+
+    pub fn to_grid(&self) -> Grid<bool> {
+        self.to_grid_with(|bit| bit)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn dims(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    fn locate(&self, x: usize, y: usize) -> Option<(usize, u64)> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let word = y * self.words_per_row + x / WORD_BITS;
+        Some((word, 1 << (x % WORD_BITS)))
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<bool> {
+        let (word, mask) = self.locate(x, y)?;
+        Some(self.data[word] & mask != 0)
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, value: bool) {
+        if let Some((word, mask)) = self.locate(x, y) {
+            if value {
+                self.data[word] |= mask;
+            } else {
+                self.data[word] &= !mask;
+            }
+        }
+    }
+
+    /// Flips the cell at `(x, y)`, returning its new value.
+    pub fn toggle(&mut self, x: usize, y: usize) -> Option<bool> {
+        let (word, mask) = self.locate(x, y)?;
+        self.data[word] ^= mask;
+        Some(self.data[word] & mask != 0)
+    }
+
+    /// Whether the point lies inside the grid.
+    pub fn contains(&self, point: Point) -> bool {
+        point.to_grid_coords(self.width, self.height).is_some()
+    }
+
+    pub fn get_point(&self, point: Point) -> Option<bool> {
+        let (x, y) = point.to_grid_coords(self.width, self.height)?;
+        self.get(x, y)
+    }
+
+    pub fn set_point(&mut self, point: Point, value: bool) {
+        if let Some((x, y)) = point.to_grid_coords(self.width, self.height) {
+            self.set(x, y, value);
+        }
+    }
+
+    pub fn set_all(&mut self, value: bool) {
+        self.data.fill(if value { u64::MAX } else { 0 });
+        if value {
+            self.clear_padding();
+        }
+    }
+
+    /// The number of set cells.
+    pub fn count_ones(&self) -> usize {
+        self.data
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// The number of set cells in row `y`.
+    ///
+    /// # Panics
+    /// Panics if `y >= height`.
+    pub fn count_ones_in_row(&self, y: usize) -> usize {
+        self.row(y)
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// Iterates over every cell along with its coordinates, row by row.
+    pub fn cells(&self) -> impl Iterator<Item = (Point, bool)> + '_ {
+        (0..self.height).flat_map(move |y| {
+            (0..self.width).map(move |x| (Point::from((x, y)), self.get(x, y).unwrap()))
+        })
+    }
+
+    /// The coordinates of every set cell, row by row.
+    pub fn ones(&self) -> impl Iterator<Item = Point> + '_ {
+        self.data
+            .chunks(self.words_per_row.max(1))
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.iter().enumerate().flat_map(move |(i, &word)| {
+                    BitIndexes(word).map(move |bit| Point::from((i * WORD_BITS + bit, y)))
+                })
+            })
+    }
+
+    fn row(&self, y: usize) -> &[u64] {
+        assert!(y < self.height, "row index {y} out of bounds");
+        &self.data[y * self.words_per_row..(y + 1) * self.words_per_row]
+    }
+
+    fn row_mut(&mut self, y: usize) -> &mut [u64] {
+        &mut self.data[y * self.words_per_row..(y + 1) * self.words_per_row]
+    }
+
+    /// Zeroes the unused bits at the end of each row.
+    fn clear_padding(&mut self) {
+        let used = self.width % WORD_BITS;
+        if used == 0 {
+            return;
+        }
+        let mask = (1 << used) - 1;
+        for y in 0..self.height {
+            *self.row_mut(y).last_mut().unwrap() &= mask;
+        }
+    }
+
+    fn zip_words(&mut self, other: &BitGrid, op: impl Fn(&mut u64, u64)) {
+        assert_eq!(self.dims(), other.dims(), "bit grids differ in size");
+        for (word, &other) in self.data.iter_mut().zip(&other.data) {
+            op(word, other);
+        }
+    }
+
+    /// A copy with every cell moved by `offset`. Cells moved past an edge are dropped and
+    /// cells moved in from outside are unset.
+    pub fn shifted(&self, offset: Offset) -> Self {
+        let mut shifted = Self::new(self.width, self.height);
+        for y in 0..self.height {
+            let target = y as i64 + offset.dy;
+            if target < 0 || target >= self.height as i64 {
+                continue;
+            }
+            let source = self.row(y);
+            let target = shifted.row_mut(target as usize);
+            shift_row(source, target, offset.dx);
+        }
+        shifted.clear_padding();
+        shifted
+    }
+
+    /// The next generation of a life-like automaton over the 8 surrounding cells. Cells
+    /// outside the grid count as unset.
+    ///
+    /// Neighbour counts are summed for 64 cells at once by adding the 8 shifted copies of
+    /// the grid as 4 bit planes.
+    pub fn step_life(&self, rule: LifeRule) -> Self {
+        let shifts: Vec<BitGrid> = [
+            (0, -1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+            (0, 1),
+            (-1, 1),
+            (-1, 0),
+            (-1, -1),
+        ]
+        .into_iter()
+        .map(|(dx, dy)| self.shifted(Offset::new(dx, dy)))
+        .collect();
+
+        let mut next = Self::new(self.width, self.height);
+        for (i, word) in next.data.iter_mut().enumerate() {
+            // ripple-carry each neighbour into a 4-bit counter per cell
+            let mut planes = [0u64; 4];
+            for shift in &shifts {
+                let mut carry = shift.data[i];
+                for plane in planes.iter_mut() {
+                    let overflow = *plane & carry;
+                    *plane ^= carry;
+                    carry = overflow;
+                }
+            }
+
+            let alive = self.data[i];
+            for count in 0..=8 {
+                let born = rule.birth & (1 << count) != 0;
+                let survives = rule.survive & (1 << count) != 0;
+                if !born && !survives {
+                    continue;
+                }
+                let has_count = planes
+                    .iter()
+                    .enumerate()
+                    .fold(u64::MAX, |acc, (bit, &plane)| {
+                        acc & if count & (1 << bit) != 0 {
+                            plane
+                        } else {
+                            !plane
+                        }
+                    });
+                let mut keep = 0;
+                if born {
+                    keep |= !alive;
+                }
+                if survives {
+                    keep |= alive;
+                }
+                *word |= has_count & keep;
+            }
+        }
+        next.clear_padding();
+        next
+    }
+}
+
+/// Copies `source` into `target` moved `dx` bits towards the end of the row.
+fn shift_row(source: &[u64], target: &mut [u64], dx: i64) {
+    let words = source.len() as i64;
+    let whole = dx.div_euclid(WORD_BITS as i64);
+    let bits = dx.rem_euclid(WORD_BITS as i64) as u32;
+    let word_at = |i: i64| {
+        if (0..words).contains(&i) {
+            source[i as usize]
+        } else {
+            0
+        }
+    };
+    for (i, word) in target.iter_mut().enumerate() {
+        let from = i as i64 - whole;
+        *word = if bits == 0 {
+            word_at(from)
+        } else {
+            (word_at(from) << bits) | (word_at(from - 1) >> (WORD_BITS as u32 - bits))
+        };
+    }
+}
+
+/// The indexes of the set bits in a word, lowest first.
+struct BitIndexes(u64);
+
+impl Iterator for BitIndexes {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.0 == 0 {
+            return None;
+        }
+        let index = self.0.trailing_zeros() as usize;
+        self.0 &= self.0 - 1;
+        Some(index)
+    }
+}
+
+/// A life-like rule in B/S notation. Bit `n` of `birth` means an unset cell with `n` set
+/// neighbours becomes set, bit `n` of `survive` means a set cell with `n` set neighbours
+/// stays set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LifeRule {
+    pub birth: u16,
+    pub survive: u16,
+}
+
+impl LifeRule {
+    /// Conway's Game of Life, B3/S23.
+    pub const CONWAY: LifeRule = LifeRule::new(&[3], &[2, 3]);
+
+    pub const fn new(birth: &[u8], survive: &[u8]) -> Self {
+        Self {
+            birth: mask(birth),
+            survive: mask(survive),
+        }
+    }
+}
+
+const fn mask(counts: &[u8]) -> u16 {
+    let mut mask = 0;
+    let mut i = 0;
+    while i < counts.len() {
+        mask |= 1 << counts[i];
+        i += 1;
+    }
+    mask
+}
+
+impl From<&Grid<bool>> for BitGrid {
+    fn from(grid: &Grid<bool>) -> Self {
+        Self::from_grid(grid, |&cell| cell)
+    }
+}
+
+impl From<&BitGrid> for Grid<bool> {
+    fn from(bits: &BitGrid) -> Self {
+        bits.to_grid()
+    }
+}
+
+impl Index<Point> for BitGrid {
+    type Output = bool;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        let (width, height) = self.dims();
+        match self.get_point(point) {
+            Some(true) => &true,
+            Some(false) => &false,
+            None => panic!("point {point:?} out of bounds for grid of size {width}x{height}"),
+        }
+    }
+}
+
+macro_rules! bit_op {
+    ($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, $op:tt) => {
+        impl $assign_trait<&BitGrid> for BitGrid {
+            /// # Panics
+            /// Panics if the grids differ in size.
+            fn $assign_method(&mut self, other: &BitGrid) {
+                self.zip_words(other, |word, other| *word $op other);
+            }
+        }
+
+        impl $trait<&BitGrid> for &BitGrid {
+            type Output = BitGrid;
+
+            fn $method(self, other: &BitGrid) -> BitGrid {
+                let mut result = self.clone();
+                $assign_trait::$assign_method(&mut result, other);
+                result
+            }
+        }
+    };
+}
+
+bit_op!(BitAnd, bitand, BitAndAssign, bitand_assign, &=);
+bit_op!(BitOr, bitor, BitOrAssign, bitor_assign, |=);
+bit_op!(BitXor, bitxor, BitXorAssign, bitxor_assign, ^=);
+
+impl Not for &BitGrid {
+    type Output = BitGrid;
+
+    fn not(self) -> BitGrid {
+        let mut result = self.clone();
+        for word in result.data.iter_mut() {
+            *word = !*word;
+        }
+        result.clear_padding();
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Neighbourhood, Topology};
+
+    fn parse(input: &str) -> BitGrid {
+        let grid: Grid<char> = input.parse().unwrap();
+        BitGrid::from_grid(&grid, |&c| c == '#')
+    }
+
+    #[test]
+    fn bulk_operations_and_shifts() {
+        // wide enough to span two words per row
+        let width = 70;
+        let mut a = BitGrid::new(width, 2);
+        a.set(0, 0, true);
+        a.set(63, 0, true);
+        a.set(69, 1, true);
+        assert_eq!(a.count_ones(), 3);
+        assert_eq!(a.count_ones_in_row(0), 2);
+        assert_eq!(
+            a.ones().collect::<Vec<_>>(),
+            [Point::new(0, 0), Point::new(63, 0), Point::new(69, 1)]
+        );
+
+        let east = a.shifted(Offset::new(1, 0));
+        assert_eq!(east.get(1, 0), Some(true));
+        assert_eq!(east.get(64, 0), Some(true));
+        assert_eq!(east.count_ones(), 2, "bit shifted past the edge is dropped");
+        let south_west = a.shifted(Offset::new(-63, 1));
+        assert_eq!(south_west.ones().collect::<Vec<_>>(), [Point::new(0, 1)]);
+
+        assert_eq!((&a & &east).count_ones(), 0);
+        assert_eq!((&a | &east).count_ones(), 5);
+        assert_eq!((&a ^ &a).count_ones(), 0);
+        assert_eq!((!&a).count_ones(), 2 * width - 3);
+    }
+
+    #[test]
+    fn conversions_round_trip() {
+        let grid: Grid<char> = "#.#\n.##\n".parse().unwrap();
+        let bits = BitGrid::from_grid(&grid, |&c| c == '#');
+        assert!(bits[Point::new(2, 1)]);
+        assert!(!bits[Point::new(1, 0)]);
+        assert_eq!(bits.to_grid_with(|bit| if bit { '#' } else { '.' }), grid);
+        let bools = bits.to_grid();
+        assert_eq!(BitGrid::from(&bools), bits);
+    }
+
+    #[test]
+    fn life_matches_automaton() {
+        let start =
+            parse(".......\n..#....\n...#...\n.###...\n.......\n.......\n.......\n.......\n");
+        let mut bits = start.clone();
+        let mut automaton = start.to_grid().automaton(Topology::Moore);
+        let life = |cell: Neighbourhood<'_, bool>| {
+            let alive = cell.count(|&alive| alive);
+            alive == 3 || (alive == 2 && *cell.value())
+        };
+        for _ in 0..8 {
+            bits = bits.step_life(LifeRule::CONWAY);
+            automaton.step(life);
+            assert_eq!(&bits.to_grid(), automaton.grid());
+        }
+        assert_eq!(bits.count_ones(), 5);
+    }
+}
@@ -0,0 +1,171 @@
+use crate::{Grid, Point};
+
+/// One axis of a compressed space. Compressed index `i` stands for the real coordinates
+/// `start(i)..start(i) + weight(i)`, so a single cell can cover a huge empty span.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompressedAxis {
+    // `starts[i]` is where index `i` begins; the final entry is where the axis ends
+    starts: Vec<i64>,
+}
+
+impl CompressedAxis {
+    /// An axis starting at `origin` whose indexes cover `weights` real units each.
+    ///
+    /// # Panics
+    /// Panics if a weight is zero.
+    pub fn from_weights(origin: i64, weights: impl IntoIterator<Item = u64>) -> Self {
+        let mut starts = vec![origin];
+        let mut end = origin;
+        for weight in weights {
+            assert!(weight > 0, "compressed cells need a positive weight");
+            end += weight as i64;
+            starts.push(end);
+        }
+        Self { starts }
+    }
+
+    /// Compresses a set of coordinates: each distinct coordinate gets its own index, and
+    /// each gap between neighbouring coordinates collapses into a single index.
+    pub fn from_coords(coords: impl IntoIterator<Item = i64>) -> Self {
+        let mut coords: Vec<i64> = coords.into_iter().collect();
+        coords.sort_unstable();
+        coords.dedup();
+        let mut starts = Vec::with_capacity(coords.len() * 2);
+        for (i, &coord) in coords.iter().enumerate() {
+            if i > 0 && coords[i - 1] + 1 < coord {
+                starts.push(coords[i - 1] + 1);
+            }
+            starts.push(coord);
+        }
+        if let Some(&last) = coords.last() {
+            starts.push(last + 1);
+        }
+        Self { starts }
+    }
+
+    /// The number of compressed indexes.
+    pub fn len(&self) -> usize {
+        self.starts.len().saturating_sub(1)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The first real coordinate covered by `index`.
+    pub fn start(&self, index: usize) -> i64 {
+        self.starts[index]
+    }
+
+    /// The number of real coordinates covered by `index`.
+    pub fn weight(&self, index: usize) -> u64 {
+        (self.starts[index + 1] - self.starts[index]) as u64
+    }
+
+    /// The index covering the real coordinate `real`, if any.
+    pub fn index_of(&self, real: i64) -> Option<usize> {
+        if self.is_empty() || real < self.starts[0] || real >= *self.starts.last()? {
+            return None;
+        }
+        Some(self.starts.partition_point(|&start| start <= real) - 1)
+    }
+}
+
+/// A 2D space where every column and row may stand for many real units, so a small
+/// [`Grid`] can represent a huge, mostly empty area.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Compression {
+    pub x: CompressedAxis,
+    pub y: CompressedAxis,
+}
+
+impl Compression {
+    /// Compresses the bounding box of `points`, collapsing the empty gaps between them.
+    pub fn from_points(points: &[Point]) -> Self {
+        Self {
+            x: CompressedAxis::from_coords(points.iter().map(|point| point.x)),
+            y: CompressedAxis::from_coords(points.iter().map(|point| point.y)),
+        }
+    }
+
+    /// Gives each column and row of `grid` a weight, e.g. so that an empty row counts as
+    /// a million. The grid's top-left corner stays at the origin.
+    pub fn from_grid<T: Clone>(
+        grid: &Grid<T>,
+        column_weight: impl Fn(usize) -> u64,
+        row_weight: impl Fn(usize) -> u64,
+    ) -> Self {
+        Self {
+            x: CompressedAxis::from_weights(0, (0..grid.width).map(column_weight)),
+            y: CompressedAxis::from_weights(0, (0..grid.height).map(row_weight)),
+        }
+    }
+
+    /// The compressed size, i.e. the size of a [`Grid`] standing for the whole space.
+    pub fn dims(&self) -> (usize, usize) {
+        (self.x.len(), self.y.len())
+    }
+
+    /// The real coordinates of the top-left corner of the compressed cell at `point`.
+    ///
+    /// # Panics
+    /// Panics if `point` is outside the compressed space.
+    pub fn real(&self, point: Point) -> Point {
+        let (x, y) = point
+            .to_grid_coords(self.x.len(), self.y.len())
+            .unwrap_or_else(|| panic!("point {point:?} out of bounds for compressed space"));
+        Point::new(self.x.start(x), self.y.start(y))
+    }
+
+    /// The compressed cell covering the real point `real`, if any.
+    pub fn compress(&self, real: Point) -> Option<Point> {
+        let x = self.x.index_of(real.x)?;
+        let y = self.y.index_of(real.y)?;
+        Some(Point::from((x, y)))
+    }
+
+    /// The number of real points covered by the compressed cell at `point`.
+    pub fn area(&self, point: Point) -> u64 {
+        let (x, y) = point
+            .to_grid_coords(self.x.len(), self.y.len())
+            .unwrap_or_else(|| panic!("point {point:?} out of bounds for compressed space"));
+        self.x.weight(x) * self.y.weight(y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compresses_gaps_between_points() {
+        let points = [Point::new(0, 0), Point::new(1_000_000, 5), Point::new(3, 5)];
+        let compression = Compression::from_points(&points);
+        // x: 0, gap, 3, gap, 1_000_000 / y: 0, gap, 5
+        assert_eq!(compression.dims(), (5, 3));
+        for point in points {
+            let compressed = compression.compress(point).unwrap();
+            assert_eq!(compression.real(compressed), point);
+            assert_eq!(compression.area(compressed), 1);
+        }
+        assert_eq!(
+            compression.compress(Point::new(500, 2)),
+            Some(Point::new(3, 1))
+        );
+        assert_eq!(compression.area(Point::new(3, 1)), 999_996 * 4);
+        assert_eq!(compression.compress(Point::new(-1, 0)), None);
+    }
+
+    #[test]
+    fn weighted_rows_and_columns() {
+        let grid = Grid::new_filled(3, 2, ());
+        let compression = Compression::from_grid(&grid, |x| if x == 1 { 10 } else { 1 }, |_| 2);
+        assert_eq!(compression.real(Point::new(2, 1)), Point::new(11, 2));
+        assert_eq!(
+            compression.compress(Point::new(5, 3)),
+            Some(Point::new(1, 1))
+        );
+        assert_eq!(compression.area(Point::new(1, 0)), 20);
+        assert_eq!(compression.x.index_of(12), None);
+    }
+}
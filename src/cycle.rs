@@ -0,0 +1,156 @@
+//! Cycle detection for sequences produced by repeatedly applying a step function.
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Where a sequence of states starts repeating. The state at step `start + length` is
+/// the same as the one at step `start`, and `start` is the first step for which that
+/// holds (μ and λ in the usual notation).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The earliest step whose state is the same as the state at step `n`.
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// Finds the cycle with Brent's algorithm. Only two states are kept at a time, so it
+/// suits states that are cheap to compare but can't be hashed. The sequence must
+/// eventually repeat, or this never returns.
+pub fn brent<S: Clone + Eq>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    // find the length by moving the tortoise to the hare at every power of two
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // then walk two pointers `length` apart from the start until they meet
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+    Cycle { start, length }
+}
+
+/// Finds the cycle by remembering every state seen, stepping each state only once.
+/// Returns the states from step 0 up to just before the repeat, so any step can be
+/// looked up afterwards. The sequence must eventually repeat, or this never returns.
+pub fn hashed<S: Clone + Eq + Hash>(initial: S, mut step: impl FnMut(&S) -> S) -> (Cycle, Vec<S>) {
+    let mut seen = HashMap::new();
+    let mut states = Vec::new();
+    let mut state = initial;
+    loop {
+        if let Some(&start) = seen.get(&state) {
+            let length = states.len() - start;
+            return (Cycle { start, length }, states);
+        }
+        seen.insert(state.clone(), states.len());
+        let next = step(&state);
+        states.push(state);
+        state = next;
+    }
+}
+
+/// The state after `n` steps, skipping over whole cycles once one is found, e.g. to
+/// spin something a billion times.
+pub fn state_at<S: Clone + Eq + Hash>(initial: S, mut step: impl FnMut(&S) -> S, n: usize) -> S {
+    let mut seen = HashMap::new();
+    let mut states = Vec::new();
+    let mut state = initial;
+    while states.len() < n {
+        if let Some(&start) = seen.get(&state) {
+            let cycle = Cycle {
+                start,
+                length: states.len() - start,
+            };
+            return states.swap_remove(cycle.equivalent_step(n));
+        }
+        seen.insert(state.clone(), states.len());
+        let next = step(&state);
+        states.push(state);
+        state = next;
+    }
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Grid;
+
+    // 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 3 -> ...
+    fn step(n: &u32) -> u32 {
+        if *n == 5 {
+            3
+        } else {
+            n + 1
+        }
+    }
+
+    #[test]
+    fn brent_and_hashed_agree() {
+        let expected = Cycle {
+            start: 3,
+            length: 3,
+        };
+        assert_eq!(brent(0, step), expected);
+        let (cycle, states) = hashed(0, step);
+        assert_eq!(cycle, expected);
+        assert_eq!(states, [0, 1, 2, 3, 4, 5]);
+        assert_eq!(
+            brent(3, step),
+            Cycle {
+                start: 0,
+                ..expected
+            }
+        );
+        assert_eq!(expected.equivalent_step(2), 2);
+        assert_eq!(expected.equivalent_step(10), 4);
+    }
+
+    #[test]
+    fn jumps_to_far_states() {
+        assert_eq!(state_at(0, step, 2), 2);
+        assert_eq!(state_at(0, step, 6), 3);
+        assert_eq!(state_at(0, step, 1_000_000_000), 4);
+
+        // rotating a grid a quarter turn cycles every 4 steps
+        let grid: Grid<char> = "ab\ncd\n".parse().unwrap();
+        let (cycle, _) = hashed(grid.clone(), Grid::rotate_cw);
+        assert_eq!(
+            cycle,
+            Cycle {
+                start: 0,
+                length: 4
+            }
+        );
+        assert_eq!(
+            state_at(grid.clone(), Grid::rotate_cw, 1_000_000_001),
+            grid.rotate_cw()
+        );
+    }
+}
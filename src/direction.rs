@@ -0,0 +1,204 @@
+use crate::{Direction, Offset};
+use std::error::Error;
+use std::fmt::Display;
+
+impl Direction {
+    /// The four cardinal directions, clockwise from north.
+    pub const CARDINALS: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// All eight directions, clockwise from north.
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    /// The position of this direction in [`Direction::ALL`].
+    fn eighths(self) -> usize {
+        Self::ALL.iter().position(|&d| d == self).unwrap()
+    }
+
+    /// Rotates clockwise by `eighths` × 45°. Negative values rotate anticlockwise.
+    pub fn rotate(self, eighths: i32) -> Self {
+        Self::ALL[(self.eighths() as i32 + eighths).rem_euclid(8) as usize]
+    }
+
+    pub fn opposite(self) -> Self {
+        self.rotate(4)
+    }
+
+    /// Turns 90° anticlockwise.
+    pub fn turn_left(self) -> Self {
+        self.rotate(-2)
+    }
+
+    /// Turns 90° clockwise.
+    pub fn turn_right(self) -> Self {
+        self.rotate(2)
+    }
+
+    /// Turns 45° anticlockwise.
+    pub fn turn_left_45(self) -> Self {
+        self.rotate(-1)
+    }
+
+    /// Turns 45° clockwise.
+    pub fn turn_right_45(self) -> Self {
+        self.rotate(1)
+    }
+
+    /// Turns 90° to the given side.
+    pub fn turn(self, turn: Turn) -> Self {
+        match turn {
+            Turn::Left => self.turn_left(),
+            Turn::Right => self.turn_right(),
+        }
+    }
+
+    /// The `(dx, dy)` of a single step, with `y` growing southwards.
+    pub fn delta(self) -> (i64, i64) {
+        let offset = self.offset();
+        (offset.dx, offset.dy)
+    }
+
+    pub fn is_cardinal(self) -> bool {
+        Self::CARDINALS.contains(&self)
+    }
+
+    /// The [`Offset`] of a single step in this direction. North is towards `y = 0`.
+    pub fn offset(self) -> Offset {
+        match self {
+            Direction::North => Offset::new(0, -1),
+            Direction::East => Offset::new(1, 0),
+            Direction::South => Offset::new(0, 1),
+            Direction::West => Offset::new(-1, 0),
+            Direction::NorthEast => Offset::new(1, -1),
+            Direction::NorthWest => Offset::new(-1, -1),
+            Direction::SouthEast => Offset::new(1, 1),
+            Direction::SouthWest => Offset::new(-1, 1),
+        }
+    }
+
+    /// The direction whose single step is `offset`, if there is one.
+    pub fn from_offset(offset: Offset) -> Option<Self> {
+        match (offset.dx, offset.dy) {
+            (0, -1) => Some(Direction::North),
+            (1, 0) => Some(Direction::East),
+            (0, 1) => Some(Direction::South),
+            (-1, 0) => Some(Direction::West),
+            (1, -1) => Some(Direction::NorthEast),
+            (-1, -1) => Some(Direction::NorthWest),
+            (1, 1) => Some(Direction::SouthEast),
+            (-1, 1) => Some(Direction::SouthWest),
+            _ => None,
+        }
+    }
+}
+
+/// Parses the usual puzzle glyphs: arrows `^>v<`, compass letters `NESW` and `UDLR`.
+impl TryFrom<char> for Direction {
+    type Error = DirectionFromCharError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '^' | 'N' | 'U' => Ok(Direction::North),
+            '>' | 'E' | 'R' => Ok(Direction::East),
+            'v' | 'S' | 'D' => Ok(Direction::South),
+            '<' | 'W' | 'L' => Ok(Direction::West),
+            _ => Err(DirectionFromCharError(c)),
+        }
+    }
+}
+
+/// A left or right turn, as in `LR` instruction strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Turn {
+    Left,
+    Right,
+}
+
+impl Turn {
+    pub fn reverse(self) -> Self {
+        match self {
+            Turn::Left => Turn::Right,
+            Turn::Right => Turn::Left,
+        }
+    }
+}
+
+impl TryFrom<char> for Turn {
+    type Error = DirectionFromCharError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'L' => Ok(Turn::Left),
+            'R' => Ok(Turn::Right),
+            _ => Err(DirectionFromCharError(c)),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`Direction`] or [`Turn`] from a
+/// character. Holds the character that wasn't recognised.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DirectionFromCharError(pub char);
+
+impl Error for DirectionFromCharError {}
+
+impl Display for DirectionFromCharError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} is not a direction", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turning_and_opposites() {
+        assert_eq!(Direction::North.opposite(), Direction::South);
+        assert_eq!(Direction::NorthEast.opposite(), Direction::SouthWest);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::West.turn_right(), Direction::North);
+        assert_eq!(Direction::North.turn_right_45(), Direction::NorthEast);
+        assert_eq!(Direction::North.turn_left_45(), Direction::NorthWest);
+        assert_eq!(Direction::South.turn(Turn::Left), Direction::East);
+        for direction in Direction::ALL {
+            assert_eq!(direction.opposite().opposite(), direction);
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            let (dx, dy) = direction.delta();
+            assert_eq!(
+                Direction::from_offset(-direction.offset()).unwrap(),
+                direction.opposite()
+            );
+            assert_eq!(direction.is_cardinal(), dx == 0 || dy == 0);
+        }
+    }
+
+    #[test]
+    fn parses_glyphs() {
+        for (glyphs, direction) in ["^NU", ">ER", "vSD", "<WL"]
+            .iter()
+            .zip(Direction::CARDINALS)
+        {
+            for c in glyphs.chars() {
+                assert_eq!(Direction::try_from(c), Ok(direction));
+            }
+        }
+        assert_eq!(Direction::try_from('x'), Err(DirectionFromCharError('x')));
+        assert_eq!(Turn::try_from('L'), Ok(Turn::Left));
+        assert_eq!(Turn::try_from('R').map(Turn::reverse), Ok(Turn::Left));
+        assert!(Turn::try_from('U').is_err());
+    }
+}
@@ -0,0 +1,183 @@
+use crate::{Bounds, Direction, Grid, Point};
+use std::collections::HashSet;
+
+/// Which neighbours count as connected when filling or labelling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Connectivity {
+    /// Only cells sharing an edge are connected.
+    Four,
+    /// Cells sharing an edge or a corner are connected.
+    Eight,
+}
+
+impl Connectivity {
+    fn directions(self) -> &'static [Direction] {
+        match self {
+            Connectivity::Four => &Direction::CARDINALS,
+            Connectivity::Eight => &Direction::ALL,
+        }
+    }
+}
+
+/// Identifies a component within a [`Components`] labelling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ComponentId(pub usize);
+
+/// A connected region of cells.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Component {
+    pub id: ComponentId,
+    pub size: usize,
+    pub bounds: Bounds,
+    /// Whether any cell of the component lies on the edge of the grid.
+    pub touches_edge: bool,
+}
+
+/// The result of [`Grid::label_components`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Components {
+    /// The component of every cell, or [`None`] for cells rejected by the predicate.
+    pub labels: Grid<Option<ComponentId>>,
+    /// Every component, indexed by its [`ComponentId`].
+    pub components: Vec<Component>,
+}
+
+impl Components {
+    pub fn get(&self, id: ComponentId) -> Option<&Component> {
+        self.components.get(id.0)
+    }
+
+    /// The component containing `point`.
+    pub fn component_at(&self, point: Point) -> Option<&Component> {
+        let id = (*self.labels.get_point(point)?)?;
+        self.get(id)
+    }
+
+    /// Components that don't reach the edge of the grid, i.e. are fully enclosed.
+    pub fn enclosed(&self) -> impl Iterator<Item = &Component> {
+        self.components.iter().filter(|c| !c.touches_edge)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Every cell that can be reached from `start` by cardinal steps through cells matching
+    /// `predicate`. Empty if `start` itself doesn't match.
+    pub fn flood_fill(&self, start: Point, predicate: impl Fn(&T) -> bool) -> HashSet<Point> {
+        let mut filled = HashSet::new();
+        self.fill(start, &predicate, Connectivity::Four, |point| {
+            filled.insert(point)
+        });
+        filled
+    }
+
+    /// Splits the cells matching `predicate` into connected components.
+    pub fn label_components(
+        &self,
+        predicate: impl Fn(&T) -> bool,
+        connectivity: Connectivity,
+    ) -> Components {
+        let mut labels: Grid<Option<ComponentId>> = Grid::new_filled(self.width, self.height, None);
+        let mut components = Vec::new();
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let start = Point::from((x, y));
+                if labels[start].is_some() || !predicate(&self[start]) {
+                    continue;
+                }
+                let id = ComponentId(components.len());
+                let mut component = Component {
+                    id,
+                    size: 0,
+                    bounds: Bounds::from_point(start),
+                    touches_edge: false,
+                };
+                self.fill(start, &predicate, connectivity, |point| {
+                    let label = &mut labels[point];
+                    if label.is_some() {
+                        return false;
+                    }
+                    *label = Some(id);
+                    component.size += 1;
+                    component.bounds.extend(point);
+                    let coords = point.to_grid_coords(self.width, self.height).unwrap();
+                    component.touches_edge |= self.is_adjacent_to_edge(coords);
+                    true
+                });
+                components.push(component);
+            }
+        }
+        Components { labels, components }
+    }
+
+    /// Depth-first fill from `start`. `visit` marks a point as seen and returns whether it
+    /// was new.
+    fn fill(
+        &self,
+        start: Point,
+        predicate: &impl Fn(&T) -> bool,
+        connectivity: Connectivity,
+        mut visit: impl FnMut(Point) -> bool,
+    ) {
+        if !self.get_point(start).is_some_and(predicate) || !visit(start) {
+            return;
+        }
+        let mut stack = vec![start];
+        while let Some(point) = stack.pop() {
+            for &direction in connectivity.directions() {
+                if let Some(next) = self.step_point(point, direction) {
+                    if predicate(&self[next]) && visit(next) {
+                        stack.push(next);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        "#####.\n#..#..\n#..#.#\n####.#\n".parse().unwrap()
+    }
+
+    #[test]
+    fn flood_fill_stays_within_predicate() {
+        let filled = grid().flood_fill(Point::new(1, 1), |&c| c == '.');
+        assert_eq!(filled.len(), 4);
+        assert!(filled.contains(&Point::new(2, 2)));
+        assert!(!filled.contains(&Point::new(4, 1)));
+
+        assert!(grid().flood_fill(Point::ORIGIN, |&c| c == '.').is_empty());
+    }
+
+    #[test]
+    fn components_report_size_bounds_and_edges() {
+        let components = grid().label_components(|&c| c == '.', Connectivity::Four);
+        assert_eq!(components.components.len(), 2);
+
+        let inner = components.component_at(Point::new(1, 1)).unwrap();
+        assert_eq!(inner.size, 4);
+        assert_eq!(
+            inner.bounds,
+            Bounds::from_points([Point::new(1, 1), Point::new(2, 2)]).unwrap()
+        );
+        assert!(!inner.touches_edge);
+
+        let outer = components.component_at(Point::new(5, 0)).unwrap();
+        assert_eq!(outer.size, 5);
+        assert!(outer.touches_edge);
+        assert_eq!(components.enclosed().count(), 1);
+    }
+
+    #[test]
+    fn eight_connectivity_joins_diagonals() {
+        let grid: Grid<char> = "#.\n.#\n".parse().unwrap();
+        let four = grid.label_components(|&c| c == '#', Connectivity::Four);
+        let eight = grid.label_components(|&c| c == '#', Connectivity::Eight);
+        assert_eq!(four.components.len(), 2);
+        assert_eq!(eight.components.len(), 1);
+        assert_eq!(eight.components[0].size, 2);
+    }
+}
@@ -0,0 +1,182 @@
+//! Exact area and lattice-point counts for closed loops on the integer grid.
+use crate::math::gcd;
+use crate::{Direction, Point};
+
+/// Measurements of a simple closed polygon whose vertices lie on integer coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoopMetrics {
+    /// Twice the signed area (shoelace formula). Positive when the vertices run clockwise
+    /// on screen, i.e. with `y` growing downwards.
+    pub double_signed_area: i128,
+    /// The number of lattice points on the boundary.
+    pub boundary: i128,
+    /// The number of lattice points strictly inside the loop (Pick's theorem).
+    pub interior: i128,
+}
+
+impl LoopMetrics {
+    /// Measures the polygon through `vertices`, in order. The last vertex connects back to
+    /// the first, so it shouldn't be repeated. Collinear intermediate vertices are fine,
+    /// which means a full list of loop tiles can be passed as is. Fewer than 3 vertices
+    /// don't enclose anything, so every measurement is zero, and neither does a loop with
+    /// no area, so it has no interior points.
+    pub fn from_vertices(vertices: &[Point]) -> Self {
+        if vertices.len() < 3 {
+            return Self {
+                double_signed_area: 0,
+                boundary: 0,
+                interior: 0,
+            };
+        }
+        let mut double_signed_area = 0;
+        let mut boundary = 0;
+        for (i, &a) in vertices.iter().enumerate() {
+            let b = vertices[(i + 1) % vertices.len()];
+            double_signed_area += a.x as i128 * b.y as i128 - b.x as i128 * a.y as i128;
+            boundary += gcd((b.x - a.x).unsigned_abs(), (b.y - a.y).unsigned_abs()) as i128;
+        }
+        // Pick's theorem: A = i + b/2 - 1, which only holds for loops that enclose an area
+        let interior = if double_signed_area == 0 {
+            0
+        } else {
+            (double_signed_area.abs() - boundary + 2) / 2
+        };
+        Self {
+            double_signed_area,
+            boundary,
+            interior,
+        }
+    }
+
+    /// Measures the loop traced by following `moves` from the origin. Only cardinal
+    /// directions make sense here, as lengths count steps.
+    pub fn from_moves(moves: impl IntoIterator<Item = (Direction, i64)>) -> Self {
+        let mut position = Point::ORIGIN;
+        let mut vertices = vec![position];
+        for (direction, length) in moves {
+            position += direction.offset() * length;
+            vertices.push(position);
+        }
+        // the final move returns to the origin, which is already the first vertex
+        if vertices.len() > 1 && vertices.last() == vertices.first() {
+            vertices.pop();
+        }
+        Self::from_vertices(&vertices)
+    }
+
+    /// The signed area, or [`None`] if it isn't a whole number.
+    pub fn signed_area(&self) -> Option<i128> {
+        (self.double_signed_area % 2 == 0).then_some(self.double_signed_area / 2)
+    }
+
+    /// The number of lattice points on or inside the loop, e.g. tiles dug out by a trench
+    /// and its interior.
+    pub fn enclosed_points(&self) -> i128 {
+        self.interior + self.boundary
+    }
+}
+
+/// The sum of the Manhattan distances between every pair of `points`.
+///
+/// Each axis is summed separately: after sorting, the `i`th coordinate is larger than
+/// the `i` before it, so it contributes `i·c` minus their sum. That makes this
+/// O(n log n) rather than a loop over every pair.
+pub fn pairwise_manhattan_sum(points: &[Point]) -> u64 {
+    fn axis_sum(mut coords: Vec<i64>) -> u64 {
+        coords.sort_unstable();
+        let mut total = 0;
+        let mut prefix: i128 = 0;
+        for (i, &coord) in coords.iter().enumerate() {
+            total += i as i128 * coord as i128 - prefix;
+            prefix += coord as i128;
+        }
+        total as u64
+    }
+
+    axis_sum(points.iter().map(|point| point.x).collect())
+        + axis_sum(points.iter().map(|point| point.y).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn square_from_vertices_and_moves() {
+        let square = [
+            Point::new(0, 0),
+            Point::new(4, 0),
+            Point::new(4, 4),
+            Point::new(0, 4),
+        ];
+        let metrics = LoopMetrics::from_vertices(&square);
+        assert_eq!(metrics.signed_area(), Some(16));
+        assert_eq!(metrics.boundary, 16);
+        assert_eq!(metrics.interior, 9);
+        assert_eq!(metrics.enclosed_points(), 25);
+
+        let reversed: Vec<_> = square.iter().rev().copied().collect();
+        assert_eq!(
+            LoopMetrics::from_vertices(&reversed).signed_area(),
+            Some(-16)
+        );
+
+        let moves = [
+            (Direction::East, 4),
+            (Direction::South, 4),
+            (Direction::West, 4),
+            (Direction::North, 4),
+        ];
+        assert_eq!(LoopMetrics::from_moves(moves), metrics);
+    }
+
+    #[test]
+    fn pairwise_sum_matches_naive() {
+        let points = [
+            Point::new(3, 0),
+            Point::new(-7, 1),
+            Point::new(0, 2),
+            Point::new(6, -4),
+            Point::new(1, 5),
+        ];
+        let mut naive = 0;
+        for (i, a) in points.iter().enumerate() {
+            for b in &points[i + 1..] {
+                naive += a.manhattan_distance(*b);
+            }
+        }
+        assert_eq!(pairwise_manhattan_sum(&points), naive);
+        assert_eq!(pairwise_manhattan_sum(&points[..1]), 0);
+    }
+
+    #[test]
+    fn half_integer_area_and_diagonal_edges() {
+        let triangle = [Point::new(0, 0), Point::new(3, 0), Point::new(0, 1)];
+        let metrics = LoopMetrics::from_vertices(&triangle);
+        assert_eq!(metrics.double_signed_area, 3);
+        assert_eq!(metrics.signed_area(), None);
+        assert_eq!(metrics.boundary, 5);
+        assert_eq!(metrics.interior, 0);
+    }
+
+    #[test]
+    fn degenerate_loops_measure_zero() {
+        let zero = LoopMetrics {
+            double_signed_area: 0,
+            boundary: 0,
+            interior: 0,
+        };
+        assert_eq!(LoopMetrics::from_vertices(&[]), zero);
+        assert_eq!(LoopMetrics::from_vertices(&[Point::new(2, 3)]), zero);
+        assert_eq!(
+            LoopMetrics::from_vertices(&[Point::ORIGIN, Point::new(5, 0)]),
+            zero
+        );
+        assert_eq!(LoopMetrics::from_moves([]), zero);
+
+        let collinear = [Point::new(0, 0), Point::new(1, 0), Point::new(2, 0)];
+        let metrics = LoopMetrics::from_vertices(&collinear);
+        assert_eq!(metrics.signed_area(), Some(0));
+        assert_eq!(metrics.interior, 0);
+    }
+}
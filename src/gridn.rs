@@ -0,0 +1,271 @@
+use crate::Grid;
+use std::ops::{Index, IndexMut};
+
+/// A dense grid with `D` axes. Coordinates are signed so that stepping off an edge can be
+/// checked rather than wrapping; the first axis varies fastest in storage, like `x` in
+/// [`Grid`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridN<T: Clone, const D: usize> {
+    dims: [usize; D],
+    data: Vec<T>,
+}
+
+pub type Grid3<T> = GridN<T, 3>;
+
+/// Which cells count as neighbours in a [`GridN`], the `D`-dimensional counterparts of
+/// [`Topology::VonNeumann`](crate::Topology::VonNeumann) and
+/// [`Topology::Moore`](crate::Topology::Moore).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Adjacency {
+    /// The `2·D` cells sharing a face.
+    Faces,
+    /// The `3^D - 1` cells sharing a face, edge or corner.
+    Full,
+}
+
+impl<T: Clone, const D: usize> GridN<T, D> {
+    pub fn new_filled(dims: [usize; D], value: T) -> Self {
+        Self {
+            dims,
+            data: vec![value; dims.iter().product()],
+        }
+    }
+
+    pub fn dims(&self) -> [usize; D] {
+        self.dims
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    fn index_of(&self, coords: [i64; D]) -> Option<usize> {
+        let mut index = 0;
+        let mut stride = 1;
+        for (&coord, &size) in coords.iter().zip(&self.dims) {
+            if coord < 0 || coord >= size as i64 {
+                return None;
+            }
+            index += coord as usize * stride;
+            stride *= size;
+        }
+        Some(index)
+    }
+
+    fn coords_of(&self, mut index: usize) -> [i64; D] {
+        let mut coords = [0; D];
+        for (coord, &size) in coords.iter_mut().zip(&self.dims) {
+            *coord = (index % size) as i64;
+            index /= size;
+        }
+        coords
+    }
+
+    /// Whether the coordinates lie inside the grid.
+    pub fn contains(&self, coords: [i64; D]) -> bool {
+        self.index_of(coords).is_some()
+    }
+
+    pub fn get(&self, coords: [i64; D]) -> Option<&T> {
+        self.index_of(coords).map(|i| &self.data[i])
+    }
+
+    pub fn get_mut(&mut self, coords: [i64; D]) -> Option<&mut T> {
+        self.index_of(coords).map(|i| &mut self.data[i])
+    }
+
+    pub fn set(&mut self, coords: [i64; D], value: T) {
+        if let Some(cell) = self.get_mut(coords) {
+            *cell = value;
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.data.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.data.iter_mut()
+    }
+
+    /// Iterates over every cell along with its coordinates, first axis fastest.
+    pub fn cells(&self) -> impl Iterator<Item = ([i64; D], &T)> {
+        self.data
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (self.coords_of(i), cell))
+    }
+
+    /// The number of cells that match `predicate`.
+    pub fn count(&self, predicate: impl Fn(&T) -> bool) -> usize {
+        self.data.iter().filter(|cell| predicate(cell)).count()
+    }
+
+    /// The neighbours of `coords` that lie inside the grid.
+    pub fn neighbours(&self, coords: [i64; D], adjacency: Adjacency) -> NeighboursN<D> {
+        NeighboursN {
+            center: coords,
+            dims: self.dims,
+            adjacency,
+            index: 0,
+        }
+    }
+
+    /// A 2D cross-section spanning `x_axis` and `y_axis`. The coordinates along every
+    /// other axis are taken from `at`.
+    ///
+    /// # Panics
+    /// Panics if an axis is out of range, the two axes are the same, or `at` is outside
+    /// the grid along one of the other axes.
+    pub fn slice(&self, x_axis: usize, y_axis: usize, at: [i64; D]) -> Grid<T> {
+        assert!(
+            x_axis < D && y_axis < D,
+            "axis out of range for {D} dimensions"
+        );
+        assert_ne!(x_axis, y_axis, "slice axes must differ");
+        let (width, height) = (self.dims[x_axis], self.dims[y_axis]);
+        let mut data = Vec::with_capacity(width * height);
+        let mut coords = at;
+        for y in 0..height {
+            for x in 0..width {
+                coords[x_axis] = x as i64;
+                coords[y_axis] = y as i64;
+                let cell = self
+                    .get(coords)
+                    .unwrap_or_else(|| panic!("slice position {at:?} out of bounds"));
+                data.push(cell.clone());
+            }
+        }
+        Grid {
+            width,
+            height,
+            data,
+        }
+    }
+}
+
+impl<T: Clone> Grid3<T> {
+    /// The horizontal layer at height `z`, with `x` across and `y` down.
+    pub fn layer(&self, z: i64) -> Grid<T> {
+        self.slice(0, 1, [0, 0, z])
+    }
+}
+
+impl<T: Clone, const D: usize> Index<[i64; D]> for GridN<T, D> {
+    type Output = T;
+
+    fn index(&self, coords: [i64; D]) -> &Self::Output {
+        let dims = self.dims;
+        self.get(coords)
+            .unwrap_or_else(|| panic!("{coords:?} out of bounds for grid of size {dims:?}"))
+    }
+}
+
+impl<T: Clone, const D: usize> IndexMut<[i64; D]> for GridN<T, D> {
+    fn index_mut(&mut self, coords: [i64; D]) -> &mut Self::Output {
+        let dims = self.dims;
+        self.get_mut(coords)
+            .unwrap_or_else(|| panic!("{coords:?} out of bounds for grid of size {dims:?}"))
+    }
+}
+
+/// Iterator over the in-bounds neighbours of a cell in a [`GridN`]. Doesn't allocate.
+#[derive(Debug, Clone)]
+pub struct NeighboursN<const D: usize> {
+    center: [i64; D],
+    dims: [usize; D],
+    adjacency: Adjacency,
+    index: usize,
+}
+
+impl<const D: usize> NeighboursN<D> {
+    fn candidates(&self) -> usize {
+        match self.adjacency {
+            Adjacency::Faces => 2 * D,
+            Adjacency::Full => 3usize.pow(D as u32),
+        }
+    }
+
+    /// The offset of the `index`th candidate, or [`None`] for the cell itself.
+    fn offset(&self, index: usize) -> Option<[i64; D]> {
+        let mut offset = [0; D];
+        match self.adjacency {
+            Adjacency::Faces => {
+                offset[index / 2] = if index.is_multiple_of(2) { -1 } else { 1 };
+            }
+            Adjacency::Full => {
+                // base-3 digits of the index, mapped to -1, 0 and 1
+                let mut rest = index;
+                for delta in offset.iter_mut() {
+                    *delta = (rest % 3) as i64 - 1;
+                    rest /= 3;
+                }
+            }
+        }
+        offset.iter().any(|&delta| delta != 0).then_some(offset)
+    }
+}
+
+impl<const D: usize> Iterator for NeighboursN<D> {
+    type Item = [i64; D];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < self.candidates() {
+            let offset = self.offset(self.index);
+            self.index += 1;
+            let Some(offset) = offset else { continue };
+            let mut coords = self.center;
+            for (coord, delta) in coords.iter_mut().zip(offset) {
+                *coord += delta;
+            }
+            let in_bounds = coords
+                .iter()
+                .zip(&self.dims)
+                .all(|(&coord, &size)| (0..size as i64).contains(&coord));
+            if in_bounds {
+                return Some(coords);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indexing_and_neighbours() {
+        let mut grid = Grid3::new_filled([3, 3, 3], 0);
+        grid[[2, 1, 0]] = 5;
+        assert_eq!(grid.get([2, 1, 0]), Some(&5));
+        assert_eq!(grid.get([3, 0, 0]), None);
+        assert_eq!(grid.get([0, -1, 0]), None);
+        assert_eq!(grid.cells().find(|(_, &v)| v == 5).unwrap().0, [2, 1, 0]);
+
+        assert_eq!(grid.neighbours([1, 1, 1], Adjacency::Faces).count(), 6);
+        assert_eq!(grid.neighbours([1, 1, 1], Adjacency::Full).count(), 26);
+        assert_eq!(grid.neighbours([0, 0, 0], Adjacency::Faces).count(), 3);
+        assert_eq!(grid.neighbours([0, 0, 0], Adjacency::Full).count(), 7);
+
+        let hyper = GridN::new_filled([3; 4], false);
+        assert_eq!(hyper.neighbours([1; 4], Adjacency::Full).count(), 80);
+        assert_eq!(hyper.neighbours([1; 4], Adjacency::Faces).count(), 8);
+    }
+
+    #[test]
+    fn slices_along_any_axis() {
+        let mut grid = Grid3::new_filled([3, 2, 2], '.');
+        grid[[0, 0, 1]] = '#';
+        grid[[2, 1, 1]] = '#';
+        let layer = grid.layer(1);
+        assert_eq!(layer, "#..\n..#\n".parse().unwrap());
+        assert_eq!(grid.layer(0), "...\n...\n".parse().unwrap());
+        // x across, z down, at y = 1
+        assert_eq!(grid.slice(0, 2, [0, 1, 0]), "...\n..#\n".parse().unwrap());
+    }
+}
@@ -1,10 +1,45 @@
+mod automaton;
+mod bitgrid;
+mod compress;
+pub mod cycle;
 mod day;
+mod direction;
+mod flood;
+pub mod geometry;
+mod gridn;
+pub mod math;
+mod neighbours;
+mod parse;
+pub mod pathfinding;
+mod point;
+pub mod ranges;
+mod ray;
+pub mod render;
+mod sparse;
+mod subgrid;
 pub mod template;
+mod tiled;
+mod transform;
 
+pub use automaton::*;
+pub use bitgrid::*;
+pub use compress::*;
 pub use day::*;
+pub use direction::*;
+pub use flood::*;
+pub use gridn::*;
+pub use neighbours::*;
+pub use parse::*;
+pub use point::*;
+pub use ray::*;
+pub use sparse::*;
+pub use subgrid::*;
+pub use tiled::*;
+
 use std::collections::HashSet;
+use std::ops::{Index, IndexMut};
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub enum Direction {
     North,
     East,
@@ -16,7 +51,7 @@ pub enum Direction {
     SouthWest,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub struct Grid<T: Clone> {
     width: usize,
     height: usize,
@@ -39,12 +74,6 @@ impl<T: Default + Clone> Grid<T> {
             data: vec![Default::default(); self.width * self.height],
         }
     }
-
-    pub fn resize(&mut self, width: usize, height: usize) {
-        self.width = width;
-        self.height = height;
-        self.data.resize(width * height, Default::default());
-    }
 }
 
 impl<T: Clone> Grid<T> {
@@ -78,12 +107,108 @@ impl<T: Clone> Grid<T> {
         self.data.get_mut(y * self.width + x)
     }
 
+    /// Whether the point lies inside the grid.
+    pub fn contains(&self, point: Point) -> bool {
+        point.to_grid_coords(self.width, self.height).is_some()
+    }
+
+    pub fn get_point(&self, point: Point) -> Option<&T> {
+        let (x, y) = point.to_grid_coords(self.width, self.height)?;
+        self.get(x, y)
+    }
+
+    pub fn get_point_mut(&mut self, point: Point) -> Option<&mut T> {
+        let (x, y) = point.to_grid_coords(self.width, self.height)?;
+        self.get_mut(x, y)
+    }
+
+    pub fn set_point(&mut self, point: Point, value: T) {
+        if let Some((x, y)) = point.to_grid_coords(self.width, self.height) {
+            self.set(x, y, value);
+        }
+    }
+
+    /// Steps from `point` in `direction`, returning [`None`] if that leaves the grid.
+    pub fn step_point(&self, point: Point, direction: Direction) -> Option<Point> {
+        let next = point + direction;
+        self.contains(next).then_some(next)
+    }
+
+    // `chunks` panics on a zero width, so rows are sliced by hand to keep `height` empty
+    // rows in a zero-width grid
     pub fn iter_rows(&self) -> impl Iterator<Item = &[T]> {
-        self.data.chunks(self.width)
+        (0..self.height).map(move |y| &self.data[y * self.width..(y + 1) * self.width])
     }
 
     pub fn iter_rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> {
-        self.data.chunks_mut(self.width)
+        let width = self.width;
+        let mut rest = &mut self.data[..];
+        (0..self.height).map(move |_| {
+            let (row, tail) = std::mem::take(&mut rest).split_at_mut(width);
+            rest = tail;
+            row
+        })
+    }
+
+    /// Iterates over the cells of row `y`, west to east.
+    ///
+    /// # Panics
+    /// Panics if `y >= height`.
+    pub fn iter_row(&self, y: usize) -> impl Iterator<Item = &T> {
+        assert!(y < self.height, "row index {y} out of bounds");
+        self.data[y * self.width..(y + 1) * self.width].iter()
+    }
+
+    /// Iterates over the cells of column `x`, north to south.
+    ///
+    /// # Panics
+    /// Panics if `x >= width`.
+    pub fn iter_col(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column index {x} out of bounds");
+        self.data.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn iter_cols(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.iter_col(x))
+    }
+
+    /// Iterates over every cell along with its coordinates, row by row.
+    pub fn cells(&self) -> impl Iterator<Item = (Point, &T)> {
+        let width = self.width;
+        self.data
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| (Point::from((i % width, i / width)), cell))
+    }
+
+    pub fn cells_mut(&mut self) -> impl Iterator<Item = (Point, &mut T)> {
+        let width = self.width;
+        self.data
+            .iter_mut()
+            .enumerate()
+            .map(move |(i, cell)| (Point::from((i % width, i / width)), cell))
+    }
+
+    /// The coordinates of the first cell, row by row, that matches `predicate`.
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<Point> {
+        self.cells()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(point, _)| point)
+    }
+
+    /// The coordinates of every cell that matches `predicate`, row by row.
+    pub fn find_all<'a>(
+        &'a self,
+        predicate: impl Fn(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Point> + 'a {
+        self.cells()
+            .filter(move |(_, cell)| predicate(cell))
+            .map(|(point, _)| point)
+    }
+
+    /// The number of cells that match `predicate`.
+    pub fn count(&self, predicate: impl Fn(&T) -> bool) -> usize {
+        self.data.iter().filter(|cell| predicate(cell)).count()
     }
 
     pub fn iter(&self) -> impl Iterator<Item = &T> {
@@ -237,6 +362,26 @@ impl<T: Clone> Grid<T> {
     }
 }
 
+impl<T: Clone> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        let (width, height) = self.dims();
+        self.get_point(point).unwrap_or_else(|| {
+            panic!("point {point:?} out of bounds for grid of size {width}x{height}")
+        })
+    }
+}
+
+impl<T: Clone> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        let (width, height) = self.dims();
+        self.get_point_mut(point).unwrap_or_else(|| {
+            panic!("point {point:?} out of bounds for grid of size {width}x{height}")
+        })
+    }
+}
+
 pub fn surrounding_indexes(
     pos: (usize, usize),
     dims_of_grid: (usize, usize),
@@ -299,4 +444,51 @@ mod test {
         assert!(set.contains(&(1, 2)));
         assert!(set.contains(&(2, 1)));
     }
+
+    #[test]
+    pub fn test_cells_and_search() {
+        let grid: Grid<char> = "ab#\n#cd\n".parse().unwrap();
+        let cells: Vec<_> = grid.cells().collect();
+        assert_eq!(cells[4], (Point::new(1, 1), &'c'));
+        assert_eq!(grid.position(|&c| c == '#'), Some(Point::new(2, 0)));
+        assert_eq!(
+            grid.find_all(|&c| c == '#').collect::<Vec<_>>(),
+            [Point::new(2, 0), Point::new(0, 1)]
+        );
+        assert_eq!(grid.count(|&c| c == '#'), 2);
+        assert_eq!(grid.position(|&c| c == 'z'), None);
+
+        assert_eq!(grid.iter_row(1).collect::<String>(), "#cd");
+        assert_eq!(grid.iter_col(2).collect::<String>(), "#d");
+        let cols: Vec<String> = grid.iter_cols().map(|col| col.collect()).collect();
+        assert_eq!(cols, ["a#", "bc", "#d"]);
+
+        let mut grid = grid;
+        for (point, cell) in grid.cells_mut() {
+            if point.x == point.y {
+                *cell = '.';
+            }
+        }
+        assert_eq!(grid.iter_row(1).collect::<String>(), "#.d");
+    }
+
+    #[test]
+    pub fn test_point_indexing() {
+        let mut grid = Grid::new_filled(3, 2, 0);
+        grid[Point::new(2, 1)] = 5;
+        assert_eq!(grid.get(2, 1), Some(&5));
+        assert_eq!(grid[Point::new(2, 1)], 5);
+        assert_eq!(grid.get_point(Point::new(1, 2)), None);
+        assert_eq!(grid.get_point(Point::new(-1, 0)), None);
+
+        assert_eq!(
+            grid.step_point(Point::new(2, 0), Direction::South),
+            Some(Point::new(2, 1))
+        );
+        assert_eq!(grid.step_point(Point::new(2, 0), Direction::East), None);
+        assert_eq!(
+            grid.step_point(Point::new(0, 0), Direction::NorthWest),
+            None
+        );
+    }
 }
@@ -0,0 +1,233 @@
+//! Number theory helpers: gcd/lcm, integer square roots, modular arithmetic and the Chinese
+//! remainder theorem.
+use std::ops::{Div, Rem};
+
+/// The unsigned integer types the gcd helpers work with.
+pub trait Unsigned: Copy + Eq + Rem<Output = Self> + Div<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl Unsigned for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other)
+                }
+
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, other)
+                }
+            }
+        )*
+    };
+}
+
+impl_unsigned!(u8, u16, u32, u64, u128, usize);
+
+/// The greatest common divisor, with `gcd(0, 0) == 0`.
+pub fn gcd<T: Unsigned>(mut a: T, mut b: T) -> T {
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The least common multiple, or [`None`] if it doesn't fit in `T`. Divides before
+/// multiplying, so only results that are genuinely too large fail.
+pub fn lcm<T: Unsigned>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// The least common multiple of every value, e.g. when several cycles line up again.
+/// [`None`] if it overflows or there are no values.
+pub fn lcm_all<T: Unsigned>(values: impl IntoIterator<Item = T>) -> Option<T> {
+    let mut values = values.into_iter();
+    let first = values.next()?;
+    values.try_fold(first, lcm)
+}
+
+/// Returns `(g, x, y)` such that `a·x + b·y = g = gcd(a, b)`, with `g` non-negative.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// The `x` in `0..modulus` with `a·x ≡ 1 (mod modulus)`, if `a` and `modulus` are
+/// coprime.
+pub fn mod_inverse(a: i128, modulus: i128) -> Option<i128> {
+    let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+    (g == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// `base^exp mod modulus` by repeated squaring.
+pub fn mod_pow(base: u64, mut exp: u64, modulus: u64) -> u64 {
+    if modulus == 1 {
+        return 0;
+    }
+    let modulus = modulus as u128;
+    let mut base = base as u128 % modulus;
+    let mut result = 1;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+    result as u64
+}
+
+/// The integer square root, i.e. the largest `r` with `r·r <= n`.
+pub fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    // Newton's method from above: the estimates shrink until they reach the root
+    let mut x = 1 << (n.ilog2() / 2 + 1);
+    loop {
+        let next = (x + n / x) / 2;
+        if next >= x {
+            return x;
+        }
+        x = next;
+    }
+}
+
+/// `a·b mod modulus` for `a` and `b` in `0..modulus`, without overflowing even when the
+/// product doesn't fit in an `i128`.
+fn mul_mod(a: i128, b: i128, modulus: i128) -> i128 {
+    if let Some(product) = a.checked_mul(b) {
+        return product % modulus;
+    }
+    // double and add: every intermediate is below 2·modulus, which fits in a u128
+    let modulus = modulus as u128;
+    let (mut a, mut b) = (a as u128, b as u128);
+    let mut result = 0;
+    while b > 0 {
+        if b & 1 == 1 {
+            result = (result + a) % modulus;
+        }
+        a = (a + a) % modulus;
+        b >>= 1;
+    }
+    result as i128
+}
+
+/// Solves the system `x ≡ residue (mod modulus)` for every `(residue, modulus)` pair.
+/// The moduli don't have to be coprime.
+///
+/// Returns `(x, m)` where `m` is the lcm of the moduli and `x` is the smallest
+/// non-negative solution, so every solution is `x + k·m`. [`None`] if the congruences
+/// contradict each other or `m` overflows.
+pub fn crt(congruences: impl IntoIterator<Item = (i128, i128)>) -> Option<(i128, i128)> {
+    let mut x: i128 = 0;
+    let mut m: i128 = 1;
+    for (residue, modulus) in congruences {
+        assert!(modulus > 0, "moduli must be positive");
+        let residue = residue.rem_euclid(modulus);
+        // x + m·k ≡ residue (mod modulus), so m·k ≡ residue - x (mod modulus)
+        let (g, inverse, _) = extended_gcd(m, modulus);
+        let difference = residue - x;
+        if difference % g != 0 {
+            return None;
+        }
+        let step = modulus / g;
+        let lcm = m.checked_mul(step)?;
+        let k = mul_mod(
+            (difference / g).rem_euclid(step),
+            inverse.rem_euclid(step),
+            step,
+        );
+        // k < step, so x + m·k < m·step = lcm and can't overflow
+        x += m * k;
+        m = lcm;
+    }
+    Some((x, m))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(gcd(12u32, 18), 6);
+        assert_eq!(gcd(0u64, 7), 7);
+        assert_eq!(lcm(4u8, 6), Some(12));
+        assert_eq!(lcm(200u8, 3), None);
+        // the product overflows a u64 but the lcm doesn't
+        let big = 1u64 << 40;
+        assert_eq!(lcm(big, big * 2), Some(big * 2));
+        assert_eq!(lcm_all([2u64, 3, 4, 5]), Some(60));
+        assert_eq!(lcm_all(Vec::<u64>::new()), None);
+    }
+
+    #[test]
+    fn modular_arithmetic() {
+        let (g, x, y) = extended_gcd(240, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(4, 8), None);
+        assert_eq!(mod_pow(4, 13, 497), 445);
+        assert_eq!(mod_pow(u64::MAX, u64::MAX, u64::MAX - 1), 1);
+    }
+
+    #[test]
+    fn integer_square_root() {
+        for n in 0..1000u128 {
+            let r = isqrt(n);
+            assert!(r * r <= n && (r + 1) * (r + 1) > n, "isqrt({n}) = {r}");
+        }
+        assert_eq!(isqrt(u64::MAX as u128), u32::MAX as u128);
+        assert_eq!(isqrt(u128::MAX), u64::MAX as u128);
+        let square = (u64::MAX as u128 - 1).pow(2);
+        assert_eq!(isqrt(square), u64::MAX as u128 - 1);
+        assert_eq!(isqrt(square - 1), u64::MAX as u128 - 2);
+    }
+
+    #[test]
+    fn chinese_remainder() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // non-coprime moduli
+        assert_eq!(crt([(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([]), Some((0, 1)));
+
+        // the lcm is about 2^100, so the intermediate products don't fit in an i128
+        let big = (1 << 80) - 65;
+        assert_eq!(
+            crt([(12345, 1_000_003), (big - 7, big)]),
+            Some((
+                466_028_814_203_243_400_557_458_729_383,
+                1_208_929_446_392_088_018_593_635_118_333
+            ))
+        );
+        assert_eq!(crt([(0, 1 << 100), (1, (1 << 100) - 1)]), None);
+    }
+}
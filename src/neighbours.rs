@@ -0,0 +1,182 @@
+use crate::{Direction, Grid, Offset, Point, SparseGrid};
+
+// axial coordinates for pointy-topped hexes: `x` is the column axis and `y` the row axis,
+// so north-west/south-east are on the `y` axis and there is no north or south.
+const HEXAGONAL: [Offset; 6] = [
+    Offset::new(1, -1),
+    Offset::new(1, 0),
+    Offset::new(0, 1),
+    Offset::new(-1, 1),
+    Offset::new(-1, 0),
+    Offset::new(0, -1),
+];
+
+/// Which cells count as neighbours of a cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Topology<'a> {
+    /// The 4 cells sharing an edge, clockwise from north.
+    VonNeumann,
+    /// The 8 cells sharing an edge or a corner, clockwise from north.
+    Moore,
+    /// The 6 cells around a hex in axial coordinates, clockwise from north-east. The hex
+    /// steps don't line up with the square-grid [`Direction`] offsets, so these neighbours
+    /// have no direction.
+    Hexagonal,
+    /// An arbitrary list of offsets, in the given order. Offsets that are a single step are
+    /// reported with their [`Direction`].
+    Custom(&'a [Offset]),
+}
+
+impl Topology<'_> {
+    /// The number of neighbours of a cell that isn't near an edge.
+    pub fn len(&self) -> usize {
+        match self {
+            Topology::VonNeumann => Direction::CARDINALS.len(),
+            Topology::Moore => Direction::ALL.len(),
+            Topology::Hexagonal => HEXAGONAL.len(),
+            Topology::Custom(offsets) => offsets.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn get(&self, index: usize) -> Option<(Offset, Option<Direction>)> {
+        match self {
+            Topology::VonNeumann => Direction::CARDINALS
+                .get(index)
+                .map(|&d| (d.offset(), Some(d))),
+            Topology::Moore => Direction::ALL.get(index).map(|&d| (d.offset(), Some(d))),
+            Topology::Hexagonal => HEXAGONAL.get(index).map(|&o| (o, None)),
+            Topology::Custom(offsets) => {
+                offsets.get(index).map(|&o| (o, Direction::from_offset(o)))
+            }
+        }
+    }
+}
+
+/// A neighbouring cell and how it was reached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Neighbour {
+    pub point: Point,
+    pub offset: Offset,
+    /// The direction whose [`Direction::offset`] is `offset`, or [`None`] if there isn't
+    /// one, e.g. for every [`Topology::Hexagonal`] neighbour.
+    pub direction: Option<Direction>,
+}
+
+/// Iterator over the neighbours of a cell, in the order defined by the [`Topology`].
+///
+/// Doesn't allocate. Neighbours outside the grid are skipped when created through
+/// [`Grid::neighbours`].
+#[derive(Debug, Clone)]
+pub struct Neighbours<'a> {
+    center: Point,
+    topology: Topology<'a>,
+    index: usize,
+    dims: Option<(usize, usize)>,
+}
+
+impl Iterator for Neighbours<'_> {
+    type Item = Neighbour;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((offset, direction)) = self.topology.get(self.index) {
+            self.index += 1;
+            let point = self.center + offset;
+            let in_bounds = self
+                .dims
+                .is_none_or(|(width, height)| point.to_grid_coords(width, height).is_some());
+            if in_bounds {
+                return Some(Neighbour {
+                    point,
+                    offset,
+                    direction,
+                });
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.topology.len() - self.index;
+        match self.dims {
+            Some(_) => (0, Some(remaining)),
+            None => (remaining, Some(remaining)),
+        }
+    }
+}
+
+/// Every neighbour of `point` on an unbounded plane.
+pub fn neighbours(point: Point, topology: Topology<'_>) -> Neighbours<'_> {
+    Neighbours {
+        center: point,
+        topology,
+        index: 0,
+        dims: None,
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// The neighbours of `point` that lie inside the grid.
+    pub fn neighbours<'a>(&self, point: Point, topology: Topology<'a>) -> Neighbours<'a> {
+        Neighbours {
+            dims: Some(self.dims()),
+            ..neighbours(point, topology)
+        }
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    /// The neighbours of `point`, whether they are stored or not.
+    pub fn neighbours<'a>(&self, point: Point, topology: Topology<'a>) -> Neighbours<'a> {
+        neighbours(point, topology)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grid_neighbours_are_ordered_and_clipped() {
+        let grid = Grid::new_filled(3, 3, ());
+        let corner: Vec<_> = grid
+            .neighbours(Point::ORIGIN, Topology::Moore)
+            .map(|n| (n.point, n.direction.unwrap()))
+            .collect();
+        assert_eq!(
+            corner,
+            [
+                (Point::new(1, 0), Direction::East),
+                (Point::new(1, 1), Direction::SouthEast),
+                (Point::new(0, 1), Direction::South),
+            ]
+        );
+        assert_eq!(
+            grid.neighbours(Point::new(1, 1), Topology::Moore).count(),
+            8
+        );
+        assert_eq!(
+            grid.neighbours(Point::new(1, 1), Topology::VonNeumann)
+                .count(),
+            4
+        );
+    }
+
+    #[test]
+    fn hexagonal_and_custom_topologies() {
+        let hex: Vec<_> = neighbours(Point::ORIGIN, Topology::Hexagonal).collect();
+        assert_eq!(hex.len(), 6);
+        assert_eq!(hex[0].point, Point::new(1, -1));
+        assert_eq!(hex[5].point, Point::new(0, -1));
+        assert!(hex.iter().all(|n| n.direction.is_none()));
+
+        let knight = [Offset::new(1, 2), Offset::new(0, 1)];
+        let custom: Vec<_> = neighbours(Point::new(5, 5), Topology::Custom(&knight)).collect();
+        assert_eq!(custom[0].point, Point::new(6, 7));
+        assert_eq!(custom[0].direction, None);
+        assert_eq!(custom[1].direction, Some(Direction::South));
+    }
+}
@@ -0,0 +1,152 @@
+use crate::Grid;
+use std::error::Error;
+use std::fmt::{Debug, Display};
+use std::str::FromStr;
+
+impl<T: Clone> Grid<T> {
+    /// Parses a grid from puzzle text, mapping every character through `parse_cell`.
+    ///
+    /// Rows are split on `\n` or `\r\n` and trailing blank lines are ignored. Every row
+    /// must have the same width as the first one.
+    pub fn parse<E, F>(input: &str, mut parse_cell: F) -> Result<Self, GridParseError<E>>
+    where
+        F: FnMut(char) -> Result<T, E>,
+    {
+        let mut lines: Vec<&str> = input.lines().collect();
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+        let width = match lines.first() {
+            Some(line) => line.chars().count(),
+            None => return Err(GridParseError::Empty),
+        };
+        let height = lines.len();
+
+        let mut data = Vec::with_capacity(width * height);
+        for (y, line) in lines.iter().enumerate() {
+            let found = line.chars().count();
+            if found != width {
+                return Err(GridParseError::RaggedRow {
+                    line: y + 1,
+                    expected: width,
+                    found,
+                });
+            }
+            for (x, c) in line.chars().enumerate() {
+                let cell = parse_cell(c).map_err(|source| GridParseError::InvalidChar {
+                    line: y + 1,
+                    column: x + 1,
+                    found: c,
+                    source,
+                })?;
+                data.push(cell);
+            }
+        }
+
+        Ok(Self {
+            width,
+            height,
+            data,
+        })
+    }
+}
+
+impl<T: Clone + TryFrom<char>> FromStr for Grid<T> {
+    type Err = GridParseError<T::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, T::try_from)
+    }
+}
+
+/// An error which can be returned when parsing a [`Grid`].
+///
+/// Line and column numbers are 1-based.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridParseError<E> {
+    /// The input contained no rows.
+    Empty,
+    /// A row's width differs from the first row's.
+    RaggedRow {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A character was rejected by the cell parser.
+    InvalidChar {
+        line: usize,
+        column: usize,
+        found: char,
+        source: E,
+    },
+}
+
+impl<E: Debug> Error for GridParseError<E> {}
+
+impl<E> Display for GridParseError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridParseError::Empty => f.write_str("expecting at least one row"),
+            GridParseError::RaggedRow {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {line} is {found} characters wide, expecting {expected}"
+            ),
+            GridParseError::InvalidChar {
+                line,
+                column,
+                found,
+                ..
+            } => write!(
+                f,
+                "invalid character {found:?} at line {line}, column {column}"
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digit(c: char) -> Result<u32, ()> {
+        c.to_digit(10).ok_or(())
+    }
+
+    #[test]
+    fn parses_crlf_and_trailing_blank_lines() {
+        let grid = Grid::parse("123\r\n456\r\n\r\n", digit).unwrap();
+        assert_eq!(grid.dims(), (3, 2));
+        assert_eq!(grid.get(0, 0), Some(&1));
+        assert_eq!(grid.get(2, 1), Some(&6));
+
+        let grid: Grid<char> = "ab\ncd\n".parse().unwrap();
+        assert_eq!(grid.dims(), (2, 2));
+        assert_eq!(grid.get(1, 1), Some(&'d'));
+    }
+
+    #[test]
+    fn reports_error_positions() {
+        assert_eq!(
+            Grid::parse("123\n45\n", digit),
+            Err(GridParseError::RaggedRow {
+                line: 2,
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            Grid::parse("123\n4x6\n", digit),
+            Err(GridParseError::InvalidChar {
+                line: 2,
+                column: 2,
+                found: 'x',
+                source: ()
+            })
+        );
+        assert_eq!(Grid::parse("\n\n", digit), Err(GridParseError::Empty));
+    }
+}
@@ -0,0 +1,355 @@
+//! Breadth-first, Dijkstra and A* search over grids and arbitrary search states.
+use crate::{Direction, Grid, Point};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+/// A node in a search. Every state is located at some grid position, but may carry
+/// extra information (heading, steps taken in a row, keys collected...).
+pub trait SearchState: Clone + Eq + Hash {
+    fn position(&self) -> Point;
+}
+
+impl SearchState for Point {
+    fn position(&self) -> Point {
+        *self
+    }
+}
+
+/// A position plus the direction of the last step and how many steps in a row were
+/// taken in that direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Heading {
+    pub position: Point,
+    pub direction: Direction,
+    pub run: u32,
+}
+
+impl Heading {
+    /// A state at `position` facing `direction` that hasn't moved yet.
+    pub fn new(position: Point, direction: Direction) -> Self {
+        Self {
+            position,
+            direction,
+            run: 0,
+        }
+    }
+
+    /// Takes a step in `direction`, extending the run if it matches the current heading.
+    pub fn advance(self, direction: Direction) -> Self {
+        let run = if direction == self.direction {
+            self.run + 1
+        } else {
+            1
+        };
+        Self {
+            position: self.position + direction,
+            direction,
+            run,
+        }
+    }
+}
+
+impl SearchState for Heading {
+    fn position(&self) -> Point {
+        self.position
+    }
+}
+
+/// The outcome of a search: the cost of every state that was reached, and the path to
+/// the goal if one was found.
+#[derive(Debug, Clone)]
+pub struct SearchResult<S> {
+    distances: HashMap<S, u64>,
+    predecessors: HashMap<S, S>,
+    goal: Option<S>,
+}
+
+impl<S: SearchState> SearchResult<S> {
+    fn new() -> Self {
+        Self {
+            distances: HashMap::new(),
+            predecessors: HashMap::new(),
+            goal: None,
+        }
+    }
+
+    /// The goal state that ended the search, if any.
+    pub fn goal(&self) -> Option<&S> {
+        self.goal.as_ref()
+    }
+
+    /// The cost of reaching the goal.
+    pub fn cost(&self) -> Option<u64> {
+        self.goal.as_ref().and_then(|goal| self.distance(goal))
+    }
+
+    pub fn distance(&self, state: &S) -> Option<u64> {
+        self.distances.get(state).copied()
+    }
+
+    /// The cost of every state reached during the search.
+    pub fn distances(&self) -> &HashMap<S, u64> {
+        &self.distances
+    }
+
+    /// The path from a start state to the goal, both included.
+    pub fn path(&self) -> Option<Vec<S>> {
+        self.path_to(self.goal.as_ref()?)
+    }
+
+    /// The path from a start state to `state`, both included.
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        if !self.distances.contains_key(state) {
+            return None;
+        }
+        let mut path = vec![state.clone()];
+        let mut current = state;
+        while let Some(previous) = self.predecessors.get(current) {
+            path.push(previous.clone());
+            current = previous;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Projects the distances onto a grid, keeping the cheapest state at each position.
+    pub fn distance_grid(&self, width: usize, height: usize) -> Grid<Option<u64>> {
+        let mut grid = Grid::new_filled(width, height, None);
+        for (state, &distance) in &self.distances {
+            if let Some(cell) = grid.get_point_mut(state.position()) {
+                if cell.is_none_or(|current| distance < current) {
+                    *cell = Some(distance);
+                }
+            }
+        }
+        grid
+    }
+}
+
+/// Breadth-first search where every step costs 1.
+///
+/// The search stops as soon as a state satisfies `is_goal`; pass `|_| false` to explore
+/// everything reachable.
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> SearchResult<S>
+where
+    S: SearchState,
+    I: IntoIterator<Item = S>,
+{
+    let mut result = SearchResult::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if !result.distances.contains_key(&start) {
+            result.distances.insert(start.clone(), 0);
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(state) = queue.pop_front() {
+        if is_goal(&state) {
+            result.goal = Some(state);
+            break;
+        }
+        let distance = result.distances[&state];
+        for next in successors(&state) {
+            if !result.distances.contains_key(&next) {
+                result.distances.insert(next.clone(), distance + 1);
+                result.predecessors.insert(next.clone(), state.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+    result
+}
+
+/// Dijkstra's algorithm. `successors` yields each next state with the cost of stepping to it.
+pub fn dijkstra<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> SearchResult<S>
+where
+    S: SearchState,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    astar(starts, successors, |_| 0, is_goal)
+}
+
+/// A* search. `heuristic` must never overestimate the remaining cost to a goal, otherwise
+/// the returned path might not be the cheapest.
+pub fn astar<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> u64,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> SearchResult<S>
+where
+    S: SearchState,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    let mut result = SearchResult::new();
+    // the heap stores indexes into `states` so that states don't need to be `Ord`
+    let mut states = Vec::new();
+    let mut heap = BinaryHeap::new();
+    for start in starts {
+        if !result.distances.contains_key(&start) {
+            result.distances.insert(start.clone(), 0);
+            heap.push(Reverse((heuristic(&start), 0, states.len())));
+            states.push(start);
+        }
+    }
+
+    while let Some(Reverse((_, distance, index))) = heap.pop() {
+        let state = states[index].clone();
+        if result.distances[&state] < distance {
+            continue;
+        }
+        if is_goal(&state) {
+            result.goal = Some(state);
+            break;
+        }
+        for (next, cost) in successors(&state) {
+            let next_distance = distance + cost;
+            if result
+                .distances
+                .get(&next)
+                .is_none_or(|&known| next_distance < known)
+            {
+                result.distances.insert(next.clone(), next_distance);
+                result.predecessors.insert(next.clone(), state.clone());
+                heap.push(Reverse((
+                    next_distance + heuristic(&next),
+                    next_distance,
+                    states.len(),
+                )));
+                states.push(next);
+            }
+        }
+    }
+    result
+}
+
+impl<T: Clone> Grid<T> {
+    /// Cardinal neighbours of `point` that lie inside the grid.
+    fn cardinal_points(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction::CARDINALS
+            .into_iter()
+            .filter_map(move |direction| self.step_point(point, direction))
+    }
+
+    /// Breadth-first search from `start` over cardinal steps onto `passable` cells,
+    /// exploring everything reachable.
+    pub fn bfs(&self, start: Point, passable: impl Fn(&T) -> bool) -> SearchResult<Point> {
+        bfs(
+            [start],
+            |&point| {
+                self.cardinal_points(point)
+                    .filter(|&next| passable(&self[next]))
+                    .collect::<Vec<_>>()
+            },
+            |_| false,
+        )
+    }
+
+    /// Cheapest path from `start` to `goal` over cardinal steps. `cost` returns the cost of
+    /// entering a cell, or [`None`] if the cell can't be entered.
+    pub fn dijkstra(
+        &self,
+        start: Point,
+        goal: Point,
+        cost: impl Fn(&T) -> Option<u64>,
+    ) -> SearchResult<Point> {
+        dijkstra(
+            [start],
+            |&point| self.weighted_cardinal_steps(point, &cost),
+            |&point| point == goal,
+        )
+    }
+
+    /// Same as [`Grid::dijkstra`], guided by the manhattan distance to `goal`. Every step
+    /// must cost at least 1 for the result to be optimal.
+    pub fn astar(
+        &self,
+        start: Point,
+        goal: Point,
+        cost: impl Fn(&T) -> Option<u64>,
+    ) -> SearchResult<Point> {
+        astar(
+            [start],
+            |&point| self.weighted_cardinal_steps(point, &cost),
+            |&point| ((goal.x - point.x).abs() + (goal.y - point.y).abs()) as u64,
+            |&point| point == goal,
+        )
+    }
+
+    fn weighted_cardinal_steps(
+        &self,
+        point: Point,
+        cost: &impl Fn(&T) -> Option<u64>,
+    ) -> Vec<(Point, u64)> {
+        self.cardinal_points(point)
+            .filter_map(|next| cost(&self[next]).map(|cost| (next, cost)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn maze() -> Grid<char> {
+        "..#.\n.##.\n....\n".parse().unwrap()
+    }
+
+    #[test]
+    fn bfs_distance_map() {
+        let result = maze().bfs(Point::new(0, 0), |&c| c == '.');
+        let distances = result.distance_grid(4, 3);
+        assert_eq!(distances.get(3, 0), Some(&Some(7)));
+        assert_eq!(distances.get(2, 0), Some(&None));
+        assert_eq!(result.path_to(&Point::new(3, 0)).unwrap().len(), 8);
+    }
+
+    #[test]
+    fn dijkstra_and_astar_agree() {
+        let grid = Grid::parse("1163\n1381\n2136\n", |c| c.to_digit(10).ok_or(())).unwrap();
+        let cost = |&c: &u32| Some(c as u64);
+        let goal = Point::new(3, 2);
+        let dijkstra = grid.dijkstra(Point::ORIGIN, goal, cost);
+        let astar = grid.astar(Point::ORIGIN, goal, cost);
+        assert_eq!(dijkstra.cost(), Some(13));
+        assert_eq!(astar.cost(), Some(13));
+
+        let path = astar.path().unwrap();
+        assert_eq!(path.first(), Some(&Point::ORIGIN));
+        assert_eq!(path.last(), Some(&goal));
+        let walked: u64 = path[1..].iter().map(|&p| grid[p] as u64).sum();
+        assert_eq!(walked, 13);
+    }
+
+    #[test]
+    fn heading_state_limits_runs() {
+        // without turning around, a straight corridor can't be crossed when at most
+        // 2 steps in a row are allowed
+        let grid: Grid<char> = ".....\n".parse().unwrap();
+        let successors = |state: &Heading| {
+            Direction::CARDINALS
+                .into_iter()
+                .filter(|direction| direction.offset() != -state.direction.offset())
+                .map(|direction| state.advance(direction))
+                .filter(|next| next.run <= 2 && grid.contains(next.position))
+                .map(|next| (next, 1))
+                .collect::<Vec<_>>()
+        };
+        let start = Heading::new(Point::ORIGIN, Direction::East);
+        let result = dijkstra([start], successors, |s| s.position == Point::new(4, 0));
+        assert_eq!(result.cost(), None);
+
+        let result = dijkstra([start], successors, |s| s.position == Point::new(2, 0));
+        assert_eq!(result.cost(), Some(2));
+    }
+}
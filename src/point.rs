@@ -0,0 +1,260 @@
+use crate::math::gcd;
+use crate::Direction;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A position on a 2D grid.
+///
+/// `x` grows towards the east and `y` grows towards the south, matching the
+/// row-major layout of [`Grid`](crate::Grid). Coordinates are signed so that
+/// stepping off the edge of a grid is representable; use
+/// [`Point::to_grid_coords`] to get back into bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// A signed displacement between two [`Point`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Offset {
+    pub dx: i64,
+    pub dy: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(0, 0);
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// Returns the `(x, y)` indexes of this point if it lies inside a grid of
+    /// the given dimensions, [`None`] otherwise.
+    pub fn to_grid_coords(self, width: usize, height: usize) -> Option<(usize, usize)> {
+        let x = usize::try_from(self.x).ok()?;
+        let y = usize::try_from(self.y).ok()?;
+        if x >= width || y >= height {
+            return None;
+        }
+        Some((x, y))
+    }
+
+    /// The point one step away in the given direction.
+    pub fn step(self, direction: Direction) -> Self {
+        self + direction.offset()
+    }
+
+    /// The number of orthogonal steps between the two points.
+    pub fn manhattan_distance(self, other: Point) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// The number of king moves between the two points, counting diagonals as one step.
+    pub fn chebyshev_distance(self, other: Point) -> u64 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// The squared straight-line distance, which stays exact and orders the same way as
+    /// the real distance.
+    pub fn euclidean_distance_squared(self, other: Point) -> u64 {
+        let dx = self.x.abs_diff(other.x);
+        let dy = self.y.abs_diff(other.y);
+        dx * dx + dy * dy
+    }
+}
+
+/// An inclusive, axis-aligned bounding box.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bounds {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Bounds {
+    /// A box containing only `point`.
+    pub fn from_point(point: Point) -> Self {
+        Self {
+            min: point,
+            max: point,
+        }
+    }
+
+    /// The smallest box containing every point, or [`None`] if there are none.
+    pub fn from_points(points: impl IntoIterator<Item = Point>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let mut bounds = Self::from_point(points.next()?);
+        for point in points {
+            bounds.extend(point);
+        }
+        Some(bounds)
+    }
+
+    /// Grows the box so it contains `point`.
+    pub fn extend(&mut self, point: Point) {
+        self.min = Point::new(self.min.x.min(point.x), self.min.y.min(point.y));
+        self.max = Point::new(self.max.x.max(point.x), self.max.y.max(point.y));
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.x - self.min.x + 1) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.y - self.min.y + 1) as usize
+    }
+}
+
+impl Offset {
+    pub const ZERO: Offset = Offset::new(0, 0);
+
+    pub const fn new(dx: i64, dy: i64) -> Self {
+        Self { dx, dy }
+    }
+
+    /// The smallest offset pointing the same way, i.e. divided by the gcd of its parts.
+    /// Every lattice point along a line of sight is a multiple of it.
+    pub fn reduced(self) -> Self {
+        let divisor = gcd(self.dx.unsigned_abs(), self.dy.unsigned_abs()).max(1) as i64;
+        Self::new(self.dx / divisor, self.dy / divisor)
+    }
+}
+
+impl From<(usize, usize)> for Point {
+    fn from((x, y): (usize, usize)) -> Self {
+        Self::new(x as i64, y as i64)
+    }
+}
+
+impl From<(i64, i64)> for Point {
+    fn from((x, y): (i64, i64)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl From<Direction> for Offset {
+    fn from(direction: Direction) -> Self {
+        direction.offset()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl Add<Offset> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Offset) -> Self::Output {
+        Point::new(self.x + rhs.dx, self.y + rhs.dy)
+    }
+}
+
+impl AddAssign<Offset> for Point {
+    fn add_assign(&mut self, rhs: Offset) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<Offset> for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Offset) -> Self::Output {
+        Point::new(self.x - rhs.dx, self.y - rhs.dy)
+    }
+}
+
+impl SubAssign<Offset> for Point {
+    fn sub_assign(&mut self, rhs: Offset) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sub<Point> for Point {
+    type Output = Offset;
+
+    fn sub(self, rhs: Point) -> Self::Output {
+        Offset::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Add<Direction> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Direction) -> Self::Output {
+        self + rhs.offset()
+    }
+}
+
+impl AddAssign<Direction> for Point {
+    fn add_assign(&mut self, rhs: Direction) {
+        *self = *self + rhs;
+    }
+}
+
+impl Add<Offset> for Offset {
+    type Output = Offset;
+
+    fn add(self, rhs: Offset) -> Self::Output {
+        Offset::new(self.dx + rhs.dx, self.dy + rhs.dy)
+    }
+}
+
+impl Sub<Offset> for Offset {
+    type Output = Offset;
+
+    fn sub(self, rhs: Offset) -> Self::Output {
+        Offset::new(self.dx - rhs.dx, self.dy - rhs.dy)
+    }
+}
+
+impl Neg for Offset {
+    type Output = Offset;
+
+    fn neg(self) -> Self::Output {
+        Offset::new(-self.dx, -self.dy)
+    }
+}
+
+impl Mul<i64> for Offset {
+    type Output = Offset;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Offset::new(self.dx * rhs, self.dy * rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn point_direction_arithmetic() {
+        let p = Point::new(2, 2);
+        assert_eq!(p + Direction::North, Point::new(2, 1));
+        assert_eq!(p + Direction::SouthWest, Point::new(1, 3));
+        assert_eq!(p + Direction::East.offset() * 3, Point::new(5, 2));
+        assert_eq!(Point::new(5, 7) - p, Offset::new(3, 5));
+        assert_eq!(p - Offset::new(3, 5), Point::new(-1, -3));
+    }
+
+    #[test]
+    fn distance_metrics() {
+        let a = Point::new(1, 6);
+        let b = Point::new(5, 11);
+        assert_eq!(a.manhattan_distance(b), 9);
+        assert_eq!(a.chebyshev_distance(b), 5);
+        assert_eq!(a.euclidean_distance_squared(b), 41);
+        assert_eq!(b.manhattan_distance(a), 9);
+    }
+
+    #[test]
+    fn grid_coords_are_bounds_checked() {
+        assert_eq!(Point::new(0, 0).to_grid_coords(3, 2), Some((0, 0)));
+        assert_eq!(Point::new(2, 1).to_grid_coords(3, 2), Some((2, 1)));
+        assert_eq!(Point::new(3, 1).to_grid_coords(3, 2), None);
+        assert_eq!(Point::new(2, 2).to_grid_coords(3, 2), None);
+        assert_eq!(Point::new(-1, 0).to_grid_coords(3, 2), None);
+    }
+}
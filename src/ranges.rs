@@ -0,0 +1,368 @@
+//! Sets of half-open ranges and piecewise maps between them.
+use std::error::Error;
+use std::fmt::{Debug, Display};
+use std::ops::{Add, Range, Sub};
+
+/// The values a [`RangeSet`] or [`RangeMap`] can hold, i.e. any integer type.
+pub trait RangeValue: Copy + Ord + Add<Output = Self> + Sub<Output = Self> {}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> RangeValue for T {}
+
+/// Moves `value` by `to - from` without going through a negative intermediate, so it
+/// works for unsigned types.
+fn translate<T: RangeValue>(value: T, from: T, to: T) -> T {
+    if to >= from {
+        value + (to - from)
+    } else {
+        value - (from - to)
+    }
+}
+
+/// A set of values stored as sorted, disjoint half-open ranges. Ranges that overlap or
+/// touch are merged, so the representation is always as small as possible.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: RangeValue> Default for RangeSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: RangeValue> RangeSet<T> {
+    pub fn new() -> Self {
+        Self { ranges: Vec::new() }
+    }
+
+    /// Adds every value in `range`, merging with the ranges it overlaps or touches.
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        // the ranges that end before `range` starts and those that start after it ends
+        // are untouched, everything in between merges into it
+        let first = self.ranges.partition_point(|r| r.end < range.start);
+        let last = self.ranges.partition_point(|r| r.start <= range.end);
+        let mut merged = range;
+        if first < last {
+            merged.start = merged.start.min(self.ranges[first].start);
+            merged.end = merged.end.max(self.ranges[last - 1].end);
+        }
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    /// The ranges in the set, in ascending order.
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Range<T>> {
+        self.ranges.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let index = self.ranges.partition_point(|r| r.end <= value);
+        self.ranges
+            .get(index)
+            .is_some_and(|range| range.contains(&value))
+    }
+
+    /// The smallest value in the set.
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|range| range.start)
+    }
+
+    pub fn union(&self, other: &RangeSet<T>) -> Self {
+        let mut union = self.clone();
+        for range in &other.ranges {
+            union.insert(range.clone());
+        }
+        union
+    }
+
+    pub fn intersection(&self, other: &RangeSet<T>) -> Self {
+        let mut intersection = Self::new();
+        let (mut i, mut j) = (0, 0);
+        while let (Some(a), Some(b)) = (self.ranges.get(i), other.ranges.get(j)) {
+            let start = a.start.max(b.start);
+            let end = a.end.min(b.end);
+            if start < end {
+                intersection.ranges.push(start..end);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        intersection
+    }
+
+    /// The values in `self` that aren't in `other`.
+    pub fn difference(&self, other: &RangeSet<T>) -> Self {
+        let mut difference = Self::new();
+        let mut j = 0;
+        for range in &self.ranges {
+            let mut start = range.start;
+            // skip ranges of `other` that end before this one starts
+            while other.ranges.get(j).is_some_and(|b| b.end <= start) {
+                j += 1;
+            }
+            let mut k = j;
+            while let Some(b) = other.ranges.get(k).filter(|b| b.start < range.end) {
+                if start < b.start {
+                    difference.ranges.push(start..b.start);
+                }
+                start = start.max(b.end);
+                k += 1;
+            }
+            if start < range.end {
+                difference.ranges.push(start..range.end);
+            }
+        }
+        difference
+    }
+
+    /// Splits the set into the values below `boundary` and those at or above it.
+    pub fn split_at(&self, boundary: T) -> (Self, Self) {
+        let mut below = Vec::new();
+        let mut above = Vec::new();
+        for range in &self.ranges {
+            if range.end <= boundary {
+                below.push(range.clone());
+            } else if range.start >= boundary {
+                above.push(range.clone());
+            } else {
+                below.push(range.start..boundary);
+                above.push(boundary..range.end);
+            }
+        }
+        (Self { ranges: below }, Self { ranges: above })
+    }
+
+    /// Moves every value by `to - from`, e.g. `translate(98, 50)` maps 98 to 50 and 99 to
+    /// 51.
+    pub fn translate(&self, from: T, to: T) -> Self {
+        Self {
+            ranges: self
+                .ranges
+                .iter()
+                .map(|range| translate(range.start, from, to)..translate(range.end, from, to))
+                .collect(),
+        }
+    }
+}
+
+impl<T: RangeValue> From<Range<T>> for RangeSet<T> {
+    fn from(range: Range<T>) -> Self {
+        let mut set = Self::new();
+        set.insert(range);
+        set
+    }
+}
+
+impl<T: RangeValue> FromIterator<Range<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+/// A piecewise-linear map: values inside a piece's source range are moved so the start
+/// of the source lands on the piece's destination, everything else maps to itself.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeMap<T> {
+    // sorted by source start, never overlapping
+    pieces: Vec<(Range<T>, T)>,
+}
+
+impl<T: RangeValue> Default for RangeMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: RangeValue> RangeMap<T> {
+    pub fn new() -> Self {
+        Self { pieces: Vec::new() }
+    }
+
+    /// Maps `source` onto the range of the same length starting at `dest`. Fails if
+    /// `source` overlaps a piece that's already in the map.
+    pub fn insert(&mut self, source: Range<T>, dest: T) -> Result<(), RangeOverlapError<T>> {
+        if source.is_empty() {
+            return Ok(());
+        }
+        // pieces are sorted and disjoint, so only the ones either side can overlap
+        let index = self.pieces.partition_point(|(s, _)| s.start < source.start);
+        let neighbours = [index.checked_sub(1), Some(index)];
+        for (existing, _) in neighbours
+            .into_iter()
+            .flatten()
+            .filter_map(|i| self.pieces.get(i))
+        {
+            if existing.start < source.end && source.start < existing.end {
+                return Err(RangeOverlapError {
+                    existing: existing.clone(),
+                    inserted: source,
+                });
+            }
+        }
+        self.pieces.insert(index, (source, dest));
+        Ok(())
+    }
+
+    /// The pieces as `(source, destination start)`, in ascending order of source.
+    pub fn pieces(&self) -> impl Iterator<Item = (&Range<T>, T)> {
+        self.pieces.iter().map(|(source, dest)| (source, *dest))
+    }
+
+    pub fn map(&self, value: T) -> T {
+        let index = self.pieces.partition_point(|(s, _)| s.end <= value);
+        match self.pieces.get(index) {
+            Some((source, dest)) if source.contains(&value) => {
+                translate(value, source.start, *dest)
+            }
+            _ => value,
+        }
+    }
+
+    /// The image of every value in `range`.
+    pub fn map_range(&self, range: Range<T>) -> RangeSet<T> {
+        let mut image = RangeSet::new();
+        let mut cursor = range.start;
+        let first = self.pieces.partition_point(|(s, _)| s.end <= range.start);
+        for (source, dest) in &self.pieces[first..] {
+            if cursor >= range.end || source.start >= range.end {
+                break;
+            }
+            // the gap before this piece maps to itself
+            if cursor < source.start {
+                image.insert(cursor..source.start);
+                cursor = source.start;
+            }
+            let end = source.end.min(range.end);
+            image.insert(
+                translate(cursor, source.start, *dest)..translate(end, source.start, *dest),
+            );
+            cursor = end;
+        }
+        if cursor < range.end {
+            image.insert(cursor..range.end);
+        }
+        image
+    }
+
+    /// The image of every value in `set`.
+    pub fn map_set(&self, set: &RangeSet<T>) -> RangeSet<T> {
+        set.iter()
+            .map(|range| self.map_range(range.clone()))
+            .fold(RangeSet::new(), |image, part| image.union(&part))
+    }
+
+    /// Every value that maps into `set`. Since pieces can land on top of each other or
+    /// on values that map to themselves, a value can have several preimages or none.
+    pub fn preimage(&self, set: &RangeSet<T>) -> RangeSet<T> {
+        let sources: RangeSet<T> = self.pieces.iter().map(|(s, _)| s.clone()).collect();
+        let mut preimage = set.difference(&sources);
+        for (source, dest) in &self.pieces {
+            let dest_end = translate(source.end, source.start, *dest);
+            let hits = set
+                .intersection(&RangeSet::from(*dest..dest_end))
+                .translate(*dest, source.start);
+            preimage = preimage.union(&hits);
+        }
+        preimage
+    }
+}
+
+/// An error returned when a [`RangeMap`] piece would overlap an existing one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeOverlapError<T> {
+    pub existing: Range<T>,
+    pub inserted: Range<T>,
+}
+
+impl<T: Debug> Error for RangeOverlapError<T> {}
+
+impl<T: Debug> Display for RangeOverlapError<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "source range {:?} overlaps existing range {:?}",
+            self.inserted, self.existing
+        )
+    }
+}
+
+#[cfg(test)]
+// a single-element slice of ranges is exactly what these tests compare against
+#[allow(clippy::single_range_in_vec_init)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[Range<u32>]) -> RangeSet<u32> {
+        ranges.iter().cloned().collect()
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = set(&[0..5, 10..15, 5..7, 20..20]);
+        assert_eq!(a.ranges(), [0..7, 10..15]);
+        assert!(a.contains(6));
+        assert!(!a.contains(7));
+        assert!(a.contains(14));
+        assert_eq!(a.min(), Some(0));
+
+        let b = set(&[3..12, 14..30]);
+        assert_eq!(a.union(&b).ranges(), [0..30]);
+        assert_eq!(a.intersection(&b).ranges(), [3..7, 10..12, 14..15]);
+        assert_eq!(a.difference(&b).ranges(), [0..3, 12..14]);
+        assert_eq!(b.difference(&a).ranges(), [7..10, 15..30]);
+
+        let (below, above) = a.split_at(12);
+        assert_eq!(below.ranges(), [0..7, 10..12]);
+        assert_eq!(above.ranges(), [12..15]);
+        assert_eq!(a.translate(0, 100).ranges(), [100..107, 110..115]);
+        assert_eq!(above.translate(12, 2).ranges(), [2..5]);
+    }
+
+    #[test]
+    fn piecewise_map() {
+        // the seed-to-soil map from day 5
+        let mut map = RangeMap::new();
+        map.insert(98..100, 50).unwrap();
+        map.insert(50..98, 52).unwrap();
+        assert_eq!(
+            map.insert(60..99, 0),
+            Err(RangeOverlapError {
+                existing: 50..98,
+                inserted: 60..99
+            })
+        );
+        assert_eq!(map.map(79), 81);
+        assert_eq!(map.map(99), 51);
+        assert_eq!(map.map(10), 10);
+        assert_eq!(map.map(100), 100);
+
+        assert_eq!(map.map_range(79..93).ranges(), [81..95]);
+        assert_eq!(map.map_range(40..105).ranges(), [40..105]);
+        assert_eq!(map.map_range(95..102).ranges(), [50..52, 97..102]);
+        let seeds = set(&[79..93, 55..68]);
+        assert_eq!(map.map_set(&seeds).ranges(), [57..70, 81..95]);
+
+        // 50 and 51 come from 98 and 99, and 98 and 99 come from 96 and 97
+        let image = set(&[50..52, 98..100]);
+        assert_eq!(map.preimage(&image).ranges(), [96..100]);
+        assert_eq!(map.preimage(&set(&[5..10])).ranges(), [5..10]);
+    }
+}
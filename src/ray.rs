@@ -0,0 +1,126 @@
+use crate::{Direction, Grid, Offset, Point};
+use std::collections::HashMap;
+
+/// Iterator over the cells seen from a starting point, stepping by a fixed offset until
+/// it leaves the grid. The starting cell itself isn't included.
+#[derive(Debug, Clone)]
+pub struct Ray<'a, T: Clone> {
+    grid: &'a Grid<T>,
+    position: Point,
+    step: Offset,
+}
+
+impl<'a, T: Clone> Ray<'a, T> {
+    /// The first cell along the ray that matches `predicate`, e.g. the nearest seat or
+    /// the first taller tree.
+    pub fn cast_until(mut self, predicate: impl Fn(&T) -> bool) -> Option<(Point, &'a T)> {
+        self.find(|(_, cell)| predicate(cell))
+    }
+}
+
+impl<'a, T: Clone> Iterator for Ray<'a, T> {
+    type Item = (Point, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.position += self.step;
+        let cell = self.grid.get_point(self.position)?;
+        Some((self.position, cell))
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// The cells from `from` to the edge of the grid in `direction`.
+    pub fn ray(&self, from: Point, direction: Direction) -> Ray<'_, T> {
+        Ray {
+            grid: self,
+            position: from,
+            step: direction.offset(),
+        }
+    }
+
+    /// The lattice points on the line from `from` through `from + vector`, out to the
+    /// edge. `vector` is reduced first, so `(2, 4)` visits every point `(1, 2)` would.
+    ///
+    /// # Panics
+    /// Panics if `vector` is zero.
+    pub fn ray_along(&self, from: Point, vector: Offset) -> Ray<'_, T> {
+        assert_ne!(vector, Offset::ZERO, "a ray needs a non-zero direction");
+        Ray {
+            grid: self,
+            position: from,
+            step: vector.reduced(),
+        }
+    }
+
+    /// The cells matching `predicate` that can be seen from `from`, i.e. that aren't
+    /// hidden behind a nearer match on the same line of sight. Returned row by row.
+    pub fn visible_from(&self, from: Point, predicate: impl Fn(&T) -> bool) -> Vec<Point> {
+        // points on the same line of sight are multiples of the same step, so any norm works
+        let distance = |point: Point| (point - from).dx.abs() + (point - from).dy.abs();
+        let mut nearest: HashMap<Offset, Point> = HashMap::new();
+        for point in self.find_all(predicate) {
+            if point == from {
+                continue;
+            }
+            let vector = point - from;
+            let blocker = nearest.entry(vector.reduced()).or_insert(point);
+            if distance(point) < distance(*blocker) {
+                *blocker = point;
+            }
+        }
+        let mut visible: Vec<Point> = nearest.into_values().collect();
+        visible.sort_by_key(|point| (point.y, point.x));
+        visible
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rays_stop_at_the_edge() {
+        let grid: Grid<char> = "abc\ndef\nghi\n".parse().unwrap();
+        let east: String = grid
+            .ray(Point::new(0, 1), Direction::East)
+            .map(|(_, &c)| c)
+            .collect();
+        assert_eq!(east, "ef");
+        let diagonal: Vec<_> = grid
+            .ray(Point::new(2, 2), Direction::NorthWest)
+            .map(|(point, _)| point)
+            .collect();
+        assert_eq!(diagonal, [Point::new(1, 1), Point::new(0, 0)]);
+        assert_eq!(grid.ray(Point::new(0, 0), Direction::North).count(), 0);
+
+        assert_eq!(
+            grid.ray(Point::new(0, 0), Direction::South)
+                .cast_until(|&c| c > 'e'),
+            Some((Point::new(0, 2), &'g'))
+        );
+        assert_eq!(
+            grid.ray(Point::new(0, 0), Direction::East)
+                .cast_until(|&c| c == 'z'),
+            None
+        );
+    }
+
+    #[test]
+    fn sloped_rays_and_visibility() {
+        assert_eq!(Offset::new(-4, 6).reduced(), Offset::new(-2, 3));
+        assert_eq!(Offset::new(0, -5).reduced(), Offset::new(0, -1));
+        assert_eq!(Offset::new(0, 0).reduced(), Offset::new(0, 0));
+
+        let grid: Grid<char> = ".#..#\n.....\n#####\n....#\n...##\n".parse().unwrap();
+        let along: Vec<_> = grid
+            .ray_along(Point::new(1, 0), Offset::new(2, 4))
+            .map(|(point, _)| point)
+            .collect();
+        assert_eq!(along, [Point::new(2, 2), Point::new(3, 4)]);
+
+        let visible = grid.visible_from(Point::new(3, 4), |&c| c == '#');
+        assert_eq!(visible.len(), 8);
+        assert!(visible.contains(&Point::new(4, 0)));
+        assert!(!visible.contains(&Point::new(1, 0)), "hidden behind (2, 2)");
+    }
+}
@@ -0,0 +1,179 @@
+//! Terminal rendering of grids for visual debugging.
+use crate::template::ANSI_RESET;
+use crate::{Grid, Point};
+use std::collections::HashSet;
+
+/// Foreground colours available to overlay [`Layer`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl Color {
+    pub fn ansi_code(self) -> &'static str {
+        match self {
+            Color::Red => "\x1b[31m",
+            Color::Green => "\x1b[32m",
+            Color::Yellow => "\x1b[33m",
+            Color::Blue => "\x1b[34m",
+            Color::Magenta => "\x1b[35m",
+            Color::Cyan => "\x1b[36m",
+            Color::White => "\x1b[37m",
+        }
+    }
+}
+
+/// A set of highlighted coordinates drawn on top of the grid.
+///
+/// Cells in the layer are coloured, and replaced by `glyph` if one is set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Layer {
+    cells: HashSet<Point>,
+    color: Color,
+    glyph: Option<char>,
+}
+
+impl Layer {
+    pub fn new(cells: impl IntoIterator<Item = Point>, color: Color) -> Self {
+        Self {
+            cells: cells.into_iter().collect(),
+            color,
+            glyph: None,
+        }
+    }
+
+    pub fn with_glyph(mut self, glyph: char) -> Self {
+        self.glyph = Some(glyph);
+        self
+    }
+}
+
+/// The rectangular region of a grid that gets rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Viewport {
+    pub origin: Point,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Viewport {
+    pub fn new(origin: Point, width: usize, height: usize) -> Self {
+        Self {
+            origin,
+            width,
+            height,
+        }
+    }
+}
+
+/// Draws grids as text, one line per row.
+///
+/// Every cell is drawn through the `glyph` mapping. Layers are applied in the order they
+/// were added, so the last layer containing a cell decides its colour and glyph.
+pub struct GridRenderer<T, F: Fn(&T) -> char> {
+    glyph: F,
+    empty: char,
+    layers: Vec<Layer>,
+    viewport: Option<Viewport>,
+    _cell: std::marker::PhantomData<fn(&T)>,
+}
+
+impl<T, F: Fn(&T) -> char> GridRenderer<T, F> {
+    pub fn new(glyph: F) -> Self {
+        Self {
+            glyph,
+            empty: ' ',
+            layers: Vec::new(),
+            viewport: None,
+            _cell: std::marker::PhantomData,
+        }
+    }
+
+    pub fn layer(mut self, layer: Layer) -> Self {
+        self.layers.push(layer);
+        self
+    }
+
+    /// Restricts rendering to a region instead of the whole grid.
+    pub fn viewport(mut self, viewport: Viewport) -> Self {
+        self.viewport = Some(viewport);
+        self
+    }
+
+    /// The character drawn for coordinates that have no cell, e.g. outside the grid.
+    pub fn empty(mut self, empty: char) -> Self {
+        self.empty = empty;
+        self
+    }
+
+    pub fn render(&self, grid: &Grid<T>) -> String
+    where
+        T: Clone,
+    {
+        let bounds = Viewport::new(Point::ORIGIN, grid.width(), grid.height());
+        self.render_region(bounds, |point| grid.get_point(point))
+    }
+
+    /// Renders `bounds` (or the configured viewport), looking up cells through `lookup`.
+    pub fn render_region<'g>(
+        &self,
+        bounds: Viewport,
+        lookup: impl Fn(Point) -> Option<&'g T>,
+    ) -> String
+    where
+        T: 'g,
+    {
+        let Viewport {
+            origin,
+            width,
+            height,
+        } = self.viewport.unwrap_or(bounds);
+        let mut out = String::with_capacity((width + 1) * height);
+        for y in origin.y..origin.y + height as i64 {
+            for x in origin.x..origin.x + width as i64 {
+                let point = Point::new(x, y);
+                let base = lookup(point).map_or(self.empty, &self.glyph);
+                match self.layers.iter().rev().find(|l| l.cells.contains(&point)) {
+                    Some(layer) => {
+                        out.push_str(layer.color.ansi_code());
+                        out.push(layer.glyph.unwrap_or(base));
+                        out.push_str(ANSI_RESET);
+                    }
+                    None => out.push(base),
+                }
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_layers_and_viewport() {
+        let grid: Grid<char> = "ab\ncd\n".parse().unwrap();
+        let plain = GridRenderer::new(|c: &char| *c).render(&grid);
+        assert_eq!(plain, "ab\ncd\n");
+
+        let highlighted = GridRenderer::new(|c: &char| *c)
+            .layer(Layer::new([Point::new(1, 0)], Color::Red))
+            .layer(Layer::new([Point::new(1, 0)], Color::Green).with_glyph('#'))
+            .render(&grid);
+        assert_eq!(highlighted, format!("a\x1b[32m#{ANSI_RESET}\ncd\n"));
+
+        let clipped = GridRenderer::new(|c: &char| *c)
+            .viewport(Viewport::new(Point::new(1, 1), 2, 1))
+            .empty('.')
+            .render(&grid);
+        assert_eq!(clipped, "d.\n");
+    }
+}
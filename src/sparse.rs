@@ -0,0 +1,191 @@
+use crate::render::{GridRenderer, Viewport};
+use crate::{Bounds, Direction, Grid, Point};
+use std::collections::{HashMap, HashSet};
+
+/// An unbounded grid that only stores occupied cells.
+///
+/// The bounding box of the stored cells is kept up to date as cells are inserted and
+/// removed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T: Clone> {
+    cells: HashMap<Point, T>,
+    bounds: Option<Bounds>,
+}
+
+impl<T: Clone> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+
+    /// Copies the cells of `grid` that match `keep`, placing the grid's top-left corner
+    /// at `origin`.
+    pub fn from_grid(grid: &Grid<T>, origin: Point, keep: impl Fn(&T) -> bool) -> Self {
+        let mut sparse = Self::new();
+        for (y, row) in grid.iter_rows().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                if keep(cell) {
+                    let point = Point::new(origin.x + x as i64, origin.y + y as i64);
+                    sparse.insert(point, cell.clone());
+                }
+            }
+        }
+        sparse
+    }
+
+    /// A dense copy covering the bounding box, with missing cells set to `empty`. The
+    /// grid's top-left corner corresponds to `bounds().min`.
+    pub fn to_grid(&self, empty: T) -> Option<Grid<T>> {
+        let bounds = self.bounds?;
+        let mut grid = Grid::new_filled(bounds.width(), bounds.height(), empty);
+        for (&point, value) in &self.cells {
+            grid[Point::ORIGIN + (point - bounds.min)] = value.clone();
+        }
+        Some(grid)
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.cells.get(&point)
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.cells.get_mut(&point)
+    }
+
+    /// Stores `value` at `point`, returning the previous value.
+    pub fn insert(&mut self, point: Point, value: T) -> Option<T> {
+        match &mut self.bounds {
+            Some(bounds) => bounds.extend(point),
+            None => self.bounds = Some(Bounds::from_point(point)),
+        }
+        self.cells.insert(point, value)
+    }
+
+    /// Removes the value at `point`, shrinking the bounding box if needed.
+    pub fn remove(&mut self, point: Point) -> Option<T> {
+        let removed = self.cells.remove(&point)?;
+        let on_edge = self.bounds.is_some_and(|bounds| {
+            point.x == bounds.min.x
+                || point.x == bounds.max.x
+                || point.y == bounds.min.y
+                || point.y == bounds.max.y
+        });
+        if on_edge {
+            self.bounds = Bounds::from_points(self.cells.keys().copied());
+        }
+        Some(removed)
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.cells.contains_key(&point)
+    }
+
+    /// The smallest box containing every stored cell, [`None`] if the grid is empty.
+    pub fn bounds(&self) -> Option<Bounds> {
+        self.bounds
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Point, &T)> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&Point, &mut T)> {
+        self.cells.iter_mut()
+    }
+
+    /// The point one step from `point` in `direction`. Never fails since the grid is
+    /// unbounded.
+    pub fn get_step(&self, direction: Direction, point: Point) -> Point {
+        point + direction
+    }
+
+    pub fn surrounding_indexes(&self, point: Point) -> HashSet<Point> {
+        Direction::ALL
+            .into_iter()
+            .map(|direction| point + direction)
+            .collect()
+    }
+
+    pub fn surrounding_cardinal_indexes(&self, point: Point) -> HashSet<Point> {
+        Direction::CARDINALS
+            .into_iter()
+            .map(|direction| point + direction)
+            .collect()
+    }
+}
+
+impl<T: Clone, F: Fn(&T) -> char> GridRenderer<T, F> {
+    /// Renders the bounding box of a [`SparseGrid`] (or the configured viewport). Missing
+    /// cells are drawn with the `empty` character.
+    pub fn render_sparse(&self, grid: &SparseGrid<T>) -> String {
+        let bounds = grid
+            .bounds()
+            .map(|b| Viewport::new(b.min, b.width(), b.height()))
+            .unwrap_or(Viewport::new(Point::ORIGIN, 0, 0));
+        self.render_region(bounds, |point| grid.get(point))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds_follow_inserts_and_removes() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+        grid.insert(Point::new(0, 0), 'a');
+        grid.insert(Point::new(-3, 2), 'b');
+        grid.insert(Point::new(1, -1), 'c');
+        assert_eq!(
+            grid.bounds(),
+            Some(Bounds {
+                min: Point::new(-3, -1),
+                max: Point::new(1, 2)
+            })
+        );
+        grid.remove(Point::new(-3, 2));
+        assert_eq!(
+            grid.bounds(),
+            Some(Bounds {
+                min: Point::new(0, -1),
+                max: Point::new(1, 0)
+            })
+        );
+        assert_eq!(
+            grid.get_step(Direction::West, Point::ORIGIN),
+            Point::new(-1, 0)
+        );
+        assert_eq!(grid.surrounding_indexes(Point::ORIGIN).len(), 8);
+    }
+
+    #[test]
+    fn dense_round_trip_and_render() {
+        let dense: Grid<char> = "#..\n..#\n".parse().unwrap();
+        let sparse = SparseGrid::from_grid(&dense, Point::new(-1, -1), |&c| c == '#');
+        assert_eq!(sparse.len(), 2);
+        assert!(sparse.contains(Point::new(1, 0)));
+        assert_eq!(sparse.to_grid('.'), Some(dense));
+
+        let rendered = GridRenderer::new(|c: &char| *c)
+            .empty('.')
+            .render_sparse(&sparse);
+        assert_eq!(rendered, "#..\n..#\n");
+    }
+}
@@ -0,0 +1,225 @@
+use crate::{Grid, Point};
+
+/// A borrowed rectangular region of a [`Grid`]. Coordinates are relative to the region's
+/// top-left corner.
+#[derive(Debug, Clone, Copy)]
+pub struct SubGrid<'a, T: Clone> {
+    grid: &'a Grid<T>,
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+}
+
+impl<'a, T: Clone> SubGrid<'a, T> {
+    pub fn get(&self, x: usize, y: usize) -> Option<&'a T> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        self.grid.get(self.x + x, self.y + y)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn dims(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    /// The position of the region's top-left corner in the underlying grid.
+    pub fn origin(&self) -> Point {
+        Point::from((self.x, self.y))
+    }
+
+    pub fn iter_rows(&self) -> impl Iterator<Item = &'a [T]> + 'a {
+        let Self {
+            grid,
+            x,
+            y,
+            width,
+            height,
+        } = *self;
+        (y..y + height).map(move |row| {
+            let start = row * grid.width + x;
+            &grid.data[start..start + width]
+        })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + 'a {
+        self.iter_rows().flatten()
+    }
+
+    /// Copies the region into its own grid.
+    pub fn to_grid(&self) -> Grid<T> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            data: self.iter().cloned().collect(),
+        }
+    }
+
+    /// Whether the region equals `pattern`, treating pattern cells equal to `wildcard`
+    /// as matching anything.
+    pub fn matches(&self, pattern: &Grid<T>, wildcard: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.dims() == pattern.dims()
+            && self
+                .iter()
+                .zip(pattern.iter())
+                .all(|(cell, expected)| expected == wildcard || cell == expected)
+    }
+}
+
+/// How a pattern was transformed to produce a match: reflected horizontally first if
+/// `flipped`, then rotated clockwise by `quarter_turns` × 90°.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Orientation {
+    pub flipped: bool,
+    pub quarter_turns: u8,
+}
+
+/// A location where a pattern was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PatternMatch {
+    /// The top-left corner of the matching region.
+    pub position: Point,
+    pub orientation: Orientation,
+}
+
+impl<T: Clone> Grid<T> {
+    /// A view of the `width` × `height` region whose top-left corner is at `(x, y)`, or
+    /// [`None`] if it doesn't fit inside the grid.
+    pub fn sub_grid(
+        &self,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+    ) -> Option<SubGrid<'_, T>> {
+        if x + width > self.width || y + height > self.height {
+            return None;
+        }
+        Some(SubGrid {
+            grid: self,
+            x,
+            y,
+            width,
+            height,
+        })
+    }
+
+    /// Every `width` × `height` region of the grid, row by row.
+    pub fn windows(&self, width: usize, height: usize) -> impl Iterator<Item = SubGrid<'_, T>> {
+        let xs = (self.width + 1).saturating_sub(width);
+        let ys = (self.height + 1).saturating_sub(height);
+        (0..ys).flat_map(move |y| (0..xs).map(move |x| self.sub_grid(x, y, width, height).unwrap()))
+    }
+
+    /// The 8 rotations and reflections of this grid. Orientations that produce the same
+    /// grid as an earlier one are skipped.
+    pub fn orientations(&self) -> Vec<(Orientation, Grid<T>)>
+    where
+        T: PartialEq,
+    {
+        let mut orientations: Vec<(Orientation, Grid<T>)> = Vec::with_capacity(8);
+        for flipped in [false, true] {
+            let mut grid = self.clone();
+            if flipped {
+                grid.flip_horizontal();
+            }
+            for quarter_turns in 0..4 {
+                if !orientations.iter().any(|(_, seen)| *seen == grid) {
+                    let orientation = Orientation {
+                        flipped,
+                        quarter_turns,
+                    };
+                    orientations.push((orientation, grid.clone()));
+                }
+                grid = grid.rotate_cw();
+            }
+        }
+        orientations
+    }
+
+    /// Every placement of `pattern` in the grid under any rotation or reflection. Pattern
+    /// cells equal to `wildcard` match anything.
+    pub fn find_pattern(&self, pattern: &Grid<T>, wildcard: &T) -> Vec<PatternMatch>
+    where
+        T: PartialEq,
+    {
+        let mut matches = Vec::new();
+        for (orientation, pattern) in pattern.orientations() {
+            for window in self.windows(pattern.width, pattern.height) {
+                if window.matches(&pattern, wildcard) {
+                    matches.push(PatternMatch {
+                        position: window.origin(),
+                        orientation,
+                    });
+                }
+            }
+        }
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        "abcd\nefgh\nijkl\n".parse().unwrap()
+    }
+
+    #[test]
+    fn sub_grid_views() {
+        let grid = grid();
+        let sub = grid.sub_grid(1, 1, 2, 2).unwrap();
+        assert_eq!(sub.get(0, 0), Some(&'f'));
+        assert_eq!(sub.get(2, 0), None);
+        let rows: Vec<String> = sub.iter_rows().map(|row| row.iter().collect()).collect();
+        assert_eq!(rows, ["fg", "jk"]);
+        assert_eq!(sub.to_grid(), "fg\njk\n".parse().unwrap());
+        assert!(grid.sub_grid(3, 0, 2, 1).is_none());
+
+        let windows: Vec<_> = grid.windows(3, 2).map(|w| w.origin()).collect();
+        assert_eq!(
+            windows,
+            [
+                Point::new(0, 0),
+                Point::new(1, 0),
+                Point::new(0, 1),
+                Point::new(1, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn find_pattern_in_all_orientations() {
+        let grid: Grid<char> = "#....\n##...\n...#.\n..##.\n".parse().unwrap();
+        let pattern: Grid<char> = "#?\n##\n".parse().unwrap();
+        let matches = grid.find_pattern(&pattern, &'?');
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].position, Point::new(0, 0));
+        assert_eq!(
+            matches[0].orientation,
+            Orientation {
+                flipped: false,
+                quarter_turns: 0
+            }
+        );
+        // mirroring an L gives the same shape as a rotation, so it's only reported once
+        assert_eq!(pattern.orientations().len(), 4);
+        assert_eq!(matches[1].position, Point::new(2, 2));
+        assert_eq!(matches[1].orientation.quarter_turns, 3);
+
+        let symmetric: Grid<char> = "##\n##\n".parse().unwrap();
+        assert_eq!(symmetric.orientations().len(), 1);
+    }
+}
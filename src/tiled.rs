@@ -0,0 +1,138 @@
+use crate::{Direction, Grid, Point};
+
+/// A view that repeats a grid infinitely in every direction.
+///
+/// Any signed coordinate maps into the base grid with Euclidean modulo, so `(-1, 0)` is
+/// the last cell of the first row.
+#[derive(Debug, Clone, Copy)]
+pub struct TiledGrid<'a, T: Clone> {
+    grid: &'a Grid<T>,
+}
+
+impl<'a, T: Clone> TiledGrid<'a, T> {
+    /// # Panics
+    /// Panics if `grid` has no cells, since there is nothing to repeat.
+    pub fn new(grid: &'a Grid<T>) -> Self {
+        assert!(
+            grid.width > 0 && grid.height > 0,
+            "can't tile an empty grid"
+        );
+        Self { grid }
+    }
+
+    /// The coordinate in the base grid that `point` is a copy of.
+    pub fn base_point(&self, point: Point) -> Point {
+        Point::new(
+            point.x.rem_euclid(self.grid.width as i64),
+            point.y.rem_euclid(self.grid.height as i64),
+        )
+    }
+
+    /// Which copy of the base grid `point` falls in. The base grid itself is tile `(0, 0)`,
+    /// the copy to its west is tile `(-1, 0)`.
+    pub fn tile_of(&self, point: Point) -> Point {
+        Point::new(
+            point.x.div_euclid(self.grid.width as i64),
+            point.y.div_euclid(self.grid.height as i64),
+        )
+    }
+
+    pub fn get(&self, point: Point) -> &'a T {
+        &self.grid[self.base_point(point)]
+    }
+}
+
+impl<'a, T: Clone> std::ops::Index<Point> for TiledGrid<'a, T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        self.get(point)
+    }
+}
+
+/// A view over a grid whose edges wrap around, like a torus.
+#[derive(Debug, Clone, Copy)]
+pub struct WrappingGrid<'a, T: Clone> {
+    grid: &'a Grid<T>,
+}
+
+impl<'a, T: Clone> WrappingGrid<'a, T> {
+    /// # Panics
+    /// Panics if `grid` has no cells, since there is nothing to wrap around to.
+    pub fn new(grid: &'a Grid<T>) -> Self {
+        assert!(
+            grid.width > 0 && grid.height > 0,
+            "can't wrap an empty grid"
+        );
+        Self { grid }
+    }
+
+    /// Gets the cell at `(x, y)`, wrapping coordinates past the edges.
+    pub fn get(&self, x: usize, y: usize) -> &'a T {
+        self.grid
+            .get(x % self.grid.width, y % self.grid.height)
+            .unwrap()
+    }
+
+    /// Same as [`Grid::get_step`], but stepping over an edge wraps to the opposite edge
+    /// instead of returning [`None`].
+    pub fn get_step(&self, direction: Direction, coords: (usize, usize)) -> (usize, usize) {
+        let point = Point::from(coords) + direction;
+        let wrapped = TiledGrid::new(self.grid).base_point(point);
+        (wrapped.x as usize, wrapped.y as usize)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Views this grid as repeating infinitely in every direction.
+    ///
+    /// # Panics
+    /// Panics if the grid has no cells.
+    pub fn tiled(&self) -> TiledGrid<'_, T> {
+        TiledGrid::new(self)
+    }
+
+    /// Views this grid with edges that wrap around.
+    ///
+    /// # Panics
+    /// Panics if the grid has no cells.
+    pub fn wrapping(&self) -> WrappingGrid<'_, T> {
+        WrappingGrid::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tiled_maps_signed_coordinates() {
+        let grid: Grid<char> = "abc\ndef\n".parse().unwrap();
+        let tiled = grid.tiled();
+        assert_eq!(tiled[Point::new(0, 0)], 'a');
+        assert_eq!(tiled[Point::new(-1, 0)], 'c');
+        assert_eq!(tiled[Point::new(4, -1)], 'e');
+        assert_eq!(tiled.tile_of(Point::new(2, 1)), Point::new(0, 0));
+        assert_eq!(tiled.tile_of(Point::new(-1, 0)), Point::new(-1, 0));
+        assert_eq!(tiled.tile_of(Point::new(7, -3)), Point::new(2, -2));
+        assert_eq!(tiled.base_point(Point::new(7, -3)), Point::new(1, 1));
+    }
+
+    #[test]
+    fn wrapping_steps_around_edges() {
+        let grid: Grid<char> = "abc\ndef\n".parse().unwrap();
+        let wrapping = grid.wrapping();
+        assert_eq!(wrapping.get_step(Direction::West, (0, 0)), (2, 0));
+        assert_eq!(wrapping.get_step(Direction::South, (1, 1)), (1, 0));
+        assert_eq!(wrapping.get_step(Direction::NorthEast, (2, 0)), (0, 1));
+        assert_eq!(wrapping.get_step(Direction::East, (0, 1)), (1, 1));
+        assert_eq!(*wrapping.get(4, 3), 'e');
+    }
+
+    #[test]
+    #[should_panic(expected = "can't tile an empty grid")]
+    fn empty_grids_cant_be_tiled() {
+        let grid: Grid<char> = Grid::new(0, 3);
+        grid.tiled();
+    }
+}
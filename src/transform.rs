@@ -0,0 +1,205 @@
+use crate::Grid;
+
+impl<T: Default + Clone> Grid<T> {
+    /// Changes the dimensions of the grid. Cells keep their `(x, y)` coordinates, cells
+    /// outside the new dimensions are dropped and new cells are filled with the default value.
+    pub fn resize(&mut self, width: usize, height: usize) {
+        let mut data = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                data.push(self.get(x, y).cloned().unwrap_or_default());
+            }
+        }
+        self.width = width;
+        self.height = height;
+        self.data = data;
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Builds a grid of the given dimensions where the cell at `(x, y)` is copied from
+    /// `source(x, y)` in this grid.
+    fn remap(
+        &self,
+        width: usize,
+        height: usize,
+        source: impl Fn(usize, usize) -> (usize, usize),
+    ) -> Self {
+        let mut data = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let (sx, sy) = source(x, y);
+                data.push(self.data[sy * self.width + sx].clone());
+            }
+        }
+        Self {
+            width,
+            height,
+            data,
+        }
+    }
+
+    /// Mirrors the grid along its main diagonal, so `(x, y)` moves to `(y, x)`.
+    pub fn transpose(&self) -> Self {
+        self.remap(self.height, self.width, |x, y| (y, x))
+    }
+
+    /// Rotates the grid by 90° clockwise.
+    pub fn rotate_cw(&self) -> Self {
+        let height = self.height;
+        self.remap(self.height, self.width, |x, y| (y, height - 1 - x))
+    }
+
+    /// Rotates the grid by 90° counter-clockwise.
+    pub fn rotate_ccw(&self) -> Self {
+        let width = self.width;
+        self.remap(self.height, self.width, |x, y| (width - 1 - y, x))
+    }
+
+    /// Mirrors the grid in place, swapping its left and right sides.
+    pub fn flip_horizontal(&mut self) {
+        for row in self.iter_rows_mut() {
+            row.reverse();
+        }
+    }
+
+    /// Mirrors the grid in place, swapping its top and bottom sides.
+    pub fn flip_vertical(&mut self) {
+        for y in 0..self.height / 2 {
+            let (top, bottom) = self.data.split_at_mut((self.height - 1 - y) * self.width);
+            top[y * self.width..(y + 1) * self.width].swap_with_slice(&mut bottom[..self.width]);
+        }
+    }
+
+    /// Inserts a row filled with `value` so that it ends up at index `y`, shifting the
+    /// rows below it down.
+    ///
+    /// # Panics
+    /// Panics if `y > height`.
+    pub fn insert_row(&mut self, y: usize, value: T) {
+        assert!(y <= self.height, "row index {y} out of bounds");
+        let at = y * self.width;
+        self.data
+            .splice(at..at, std::iter::repeat_n(value, self.width));
+        self.height += 1;
+    }
+
+    /// Inserts a column filled with `value` so that it ends up at index `x`, shifting the
+    /// columns to its right over.
+    ///
+    /// # Panics
+    /// Panics if `x > width`.
+    pub fn insert_column(&mut self, x: usize, value: T) {
+        assert!(x <= self.width, "column index {x} out of bounds");
+        if self.width == 0 {
+            self.width = 1;
+            self.data = vec![value; self.height];
+            return;
+        }
+        let mut data = Vec::with_capacity((self.width + 1) * self.height);
+        for row in self.iter_rows() {
+            data.extend_from_slice(&row[..x]);
+            data.push(value.clone());
+            data.extend_from_slice(&row[x..]);
+        }
+        self.width += 1;
+        self.data = data;
+    }
+
+    /// Removes and returns the row at index `y`.
+    ///
+    /// # Panics
+    /// Panics if `y >= height`.
+    pub fn remove_row(&mut self, y: usize) -> Vec<T> {
+        assert!(y < self.height, "row index {y} out of bounds");
+        let at = y * self.width;
+        self.height -= 1;
+        self.data.drain(at..at + self.width).collect()
+    }
+
+    /// Removes and returns the column at index `x`.
+    ///
+    /// # Panics
+    /// Panics if `x >= width`.
+    pub fn remove_column(&mut self, x: usize) -> Vec<T> {
+        assert!(x < self.width, "column index {x} out of bounds");
+        if self.width == 1 {
+            self.width = 0;
+            return std::mem::take(&mut self.data);
+        }
+        let mut removed = Vec::with_capacity(self.height);
+        let mut data = Vec::with_capacity((self.width - 1) * self.height);
+        for row in self.iter_rows() {
+            data.extend_from_slice(&row[..x]);
+            removed.push(row[x].clone());
+            data.extend_from_slice(&row[x + 1..]);
+        }
+        self.width -= 1;
+        self.data = data;
+        removed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        "abc\ndef\n".parse().unwrap()
+    }
+
+    fn rows(grid: &Grid<char>) -> Vec<String> {
+        grid.iter_rows().map(|row| row.iter().collect()).collect()
+    }
+
+    #[test]
+    fn rotations_and_flips() {
+        assert_eq!(rows(&grid().transpose()), ["ad", "be", "cf"]);
+        assert_eq!(rows(&grid().rotate_cw()), ["da", "eb", "fc"]);
+        assert_eq!(rows(&grid().rotate_ccw()), ["cf", "be", "ad"]);
+        assert_eq!(grid().rotate_cw().rotate_ccw(), grid());
+
+        let mut flipped = grid();
+        flipped.flip_horizontal();
+        assert_eq!(rows(&flipped), ["cba", "fed"]);
+
+        let mut flipped: Grid<char> = "ab\ncd\nef\n".parse().unwrap();
+        flipped.flip_vertical();
+        assert_eq!(rows(&flipped), ["ef", "cd", "ab"]);
+    }
+
+    #[test]
+    fn insert_and_remove_rows_and_columns() {
+        let mut grid = grid();
+        grid.insert_row(1, '.');
+        assert_eq!(rows(&grid), ["abc", "...", "def"]);
+        grid.insert_column(3, '|');
+        assert_eq!(rows(&grid), ["abc|", "...|", "def|"]);
+        assert_eq!(grid.remove_column(0), ['a', '.', 'd']);
+        assert_eq!(grid.remove_row(1), ['.', '.', '|']);
+        assert_eq!(rows(&grid), ["bc|", "ef|"]);
+        assert_eq!(grid.dims(), (3, 2));
+    }
+
+    #[test]
+    fn columns_of_zero_width_grids() {
+        let mut grid = Grid::new_filled(0, 3, 'x');
+        assert_eq!(rows(&grid), ["", "", ""]);
+        grid.insert_column(0, '|');
+        assert_eq!(rows(&grid), ["|", "|", "|"]);
+        assert_eq!(grid.remove_column(0), ['|', '|', '|']);
+        assert_eq!(grid.dims(), (0, 3));
+        assert_eq!(rows(&grid), ["", "", ""]);
+        grid.insert_column(0, '.');
+        assert_eq!(rows(&grid), [".", ".", "."]);
+    }
+
+    #[test]
+    fn resize_keeps_coordinates() {
+        let mut grid = grid();
+        grid.resize(4, 3);
+        assert_eq!(rows(&grid), ["abc\0", "def\0", "\0\0\0\0"]);
+        grid.resize(2, 1);
+        assert_eq!(rows(&grid), ["ab"]);
+    }
+}